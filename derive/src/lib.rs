@@ -0,0 +1,269 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Data, DeriveInput, Error, Expr, ExprRange, Fields,
+    GenericArgument, LitInt, LitStr, PathArguments, RangeLimits, Token, Type,
+};
+
+/// Implements `tuwi::TuwiForm`, picking a widget for each field by its type:
+///
+/// | Type                      | Widget                                     |
+/// | ------------------------- | ------------------------------------------ |
+/// | `String`                  | `Input` (`Secret` / `Radio` by attribute)  |
+/// | integers                  | `NumInput` (`Slider` with `slider`)        |
+/// | `f32`, `f64`              | `Input`                                    |
+/// | `bool`                    | `Checkbox`                                 |
+/// | `Vec<String>`             | `List`                                     |
+///
+/// Fields accept `#[tuwi(...)]` with:
+/// - `title = "Port"`: the item's title (defaults to the field name)
+/// - `name = "server.port"`: the item's name (defaults to the field name)
+/// - `range = 1..=65535`, `step = 5`: for integer fields
+/// - `slider`, `units = "%"`: use a `Slider` for an integer field
+/// - `secret`: use a `Secret` for a `String` field
+/// - `choices("tcp", "udp")`: use a `Radio` for a `String` field
+/// - `skip`: leave the field out, filling it from `Default` when read back
+#[proc_macro_derive(TuwiForm, attributes(tuwi))]
+pub fn derive_tuwi_form(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Attrs {
+    title: Option<String>,
+    name: Option<String>,
+    range: Option<(Expr, Expr)>,
+    step: Option<LitInt>,
+    slider: bool,
+    units: Option<String>,
+    secret: bool,
+    choices: Option<Vec<LitStr>>,
+    skip: bool,
+}
+
+fn parse_attrs(field: &syn::Field) -> syn::Result<Attrs> {
+    let mut attrs = Attrs::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("tuwi")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("title") {
+                attrs.title = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("name") {
+                attrs.name = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("range") {
+                let range: ExprRange = meta.value()?.parse()?;
+                let (Some(start), Some(end)) = (range.start, range.end) else {
+                    return Err(meta.error("expected a bounded range, e.g. `1..=65535`"));
+                };
+
+                let end = match range.limits {
+                    RangeLimits::Closed(_) => *end,
+                    RangeLimits::HalfOpen(_) => syn::parse_quote!((#end) - 1),
+                };
+
+                attrs.range = Some((*start, end));
+            } else if meta.path.is_ident("step") {
+                attrs.step = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("slider") {
+                attrs.slider = true;
+            } else if meta.path.is_ident("units") {
+                attrs.units = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("secret") {
+                attrs.secret = true;
+            } else if meta.path.is_ident("choices") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let choices = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                attrs.choices = Some(choices.into_iter().collect());
+            } else if meta.path.is_ident("skip") {
+                attrs.skip = true;
+            } else {
+                return Err(meta.error("unknown tuwi attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(attrs)
+}
+
+/// The last path segment of `ty`, e.g. `String` or `Vec`, with its first
+/// generic argument if any.
+fn type_name(ty: &Type) -> Option<(String, Option<&Type>)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+    let arg = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|a| match a {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        }),
+        _ => None,
+    };
+
+    Some((segment.ident.to_string(), arg))
+}
+
+fn default_title(field: &str) -> String {
+    let spaced = field.replace('_', " ");
+    let mut chars = spaced.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => spaced,
+    }
+}
+
+const INTEGERS: [&str; 10] = [
+    "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize",
+];
+
+fn widget(field: &syn::Field, attrs: &Attrs, name: &str) -> syn::Result<TokenStream2> {
+    let ident = field.ident.as_ref().unwrap();
+    let title = attrs
+        .title
+        .clone()
+        .unwrap_or_else(|| default_title(&ident.to_string()));
+
+    let ty = &field.ty;
+    let Some((kind, arg)) = type_name(ty) else {
+        return Err(Error::new_spanned(
+            ty,
+            "unsupported field type, use #[tuwi(skip)]",
+        ));
+    };
+
+    Ok(match kind.as_str() {
+        "String" if attrs.secret => quote! {
+            ::tuwi::Secret::new(#name, #title, &self.#ident)
+        },
+        "String" => match &attrs.choices {
+            Some(choices) => quote! {
+                ::tuwi::Radio::new(#name, #title, vec![#((#choices, #choices)),*], &self.#ident)
+            },
+            None => quote! {
+                ::tuwi::Input::new(#name, #title, &self.#ident)
+            },
+        },
+        t if INTEGERS.contains(&t) && attrs.slider => {
+            let (min, max) = attrs
+                .range
+                .clone()
+                .unwrap_or((syn::parse_quote!(0), syn::parse_quote!(100)));
+            let units = match &attrs.units {
+                Some(u) => quote!(Some(#u)),
+                None => quote!(None),
+            };
+
+            quote! {
+                ::tuwi::Slider::new(
+                    #name,
+                    #title,
+                    ((#min) as i32, (#max) as i32),
+                    ::tuwi::int_field::<i32, _>(#name, self.#ident)?,
+                    #units,
+                )
+            }
+        }
+        t if INTEGERS.contains(&t) => {
+            let (min, max) = match attrs.range.clone() {
+                Some((min, max)) => (quote!((#min) as i64), quote!((#max) as i64)),
+                None => (
+                    quote!((#ty::MIN as i128).max(i64::MIN as i128) as i64),
+                    quote!((#ty::MAX as i128).min(i64::MAX as i128) as i64),
+                ),
+            };
+
+            let step = attrs.step.as_ref().map(|s| quote!(#s)).unwrap_or(quote!(1));
+
+            quote! {
+                ::tuwi::NumInput::new(
+                    #name,
+                    #title,
+                    ::tuwi::int_field::<i64, _>(#name, self.#ident)?,
+                    (#min, #max),
+                    #step,
+                )
+            }
+        }
+        "f32" | "f64" => quote! {
+            ::tuwi::Input::new(#name, #title, &self.#ident.to_string())
+        },
+        "bool" => quote! {
+            ::tuwi::Checkbox::new(#name, #title, self.#ident)
+        },
+        "Vec" if arg.and_then(type_name).is_some_and(|(t, _)| t == "String") => quote! {
+            ::tuwi::List::new(#name, #title, self.#ident.clone())
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                ty,
+                "unsupported field type, use #[tuwi(skip)]",
+            ))
+        }
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input,
+            "TuwiForm can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input,
+            "TuwiForm needs a struct with named fields",
+        ));
+    };
+
+    let mut pushes = Vec::new();
+    let mut reads = Vec::new();
+    for field in &fields.named {
+        let attrs = parse_attrs(field)?;
+        let field_ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        if attrs.skip {
+            reads.push(quote!(#field_ident: ::core::default::Default::default()));
+            continue;
+        }
+
+        let name = attrs
+            .name
+            .clone()
+            .unwrap_or_else(|| field_ident.to_string());
+        let widget = widget(field, &attrs, &name)?;
+
+        pushes.push(quote!(form.push(#widget);));
+        reads.push(quote! {
+            #field_ident: <#ty as ::tuwi::FromFormValue>::from_field(&values, #name)?
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::tuwi::TuwiForm for #ident #ty_generics #where_clause {
+            fn try_to_form(&self) -> ::core::result::Result<::tuwi::Form, ::tuwi::FieldError> {
+                let mut form = ::tuwi::Form::new();
+                #(#pushes)*
+                ::core::result::Result::Ok(form)
+            }
+
+            fn from_form(form: &::tuwi::Form) -> ::core::result::Result<Self, ::tuwi::FieldError> {
+                let values = form.values();
+                ::core::result::Result::Ok(Self {
+                    #(#reads),*
+                })
+            }
+        }
+    })
+}
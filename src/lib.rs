@@ -1,9 +1,12 @@
 pub mod widgets;
 pub use widgets::*;
 
+#[cfg(feature = "derive")]
+pub use tuwi_derive::TuwiForm;
+
 #[macro_export]
 macro_rules! ab {
     ($e: expr) => {
         Arc::new(Box::new($e))
     };
-}
\ No newline at end of file
+}
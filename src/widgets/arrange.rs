@@ -0,0 +1,262 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use super::{conditions::ItemState, Form};
+
+/// How a [`Form`] arranges its items. Set with [`Form::set_layout`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FormLayout {
+    /// One item (or [row](Form::row)) under another.
+    #[default]
+    Stacked,
+    /// Items flow down this many columns, in order, balancing their heights.
+    ///
+    /// Left / Right move between columns from items that don't use them,
+    /// like buttons and checkboxes. Editing items (inputs, lists, radios,
+    /// sliders) keep plain Left / Right, so from those it takes Alt+Left /
+    /// Right.
+    Columns(u16),
+    /// Stacked, with each item's label in a gutter this wide to its left
+    /// instead of in its border.
+    LabelLeft(u16),
+}
+
+/// Where one item goes, in form content coordinates.
+#[derive(Clone, Copy, Default)]
+pub(super) struct Slot {
+    pub item: Rect,
+    pub label: Option<Rect>,
+}
+
+/// Which way arrow-key navigation moves.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Nav {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Form {
+    /// Switches layout, asking items to leave their own titles out when the
+    /// form draws labels beside them.
+    pub fn set_layout(&mut self, layout: FormLayout) {
+        self.layout = layout;
+        let outside = matches!(layout, FormLayout::LabelLeft(_));
+        for item in &self.items {
+            item.write().unwrap().set_show_title(!outside);
+        }
+    }
+
+    pub fn layout(&self) -> FormLayout {
+        self.layout
+    }
+
+    /// Puts the named items side by side, sharing the width of one. The row
+    /// sits where the first of them would have. Moving along it works as in
+    /// [`FormLayout::Columns`].
+    pub fn row(&mut self, names: &[&str]) {
+        self.rows
+            .push(names.iter().map(|n| n.to_string()).collect());
+    }
+
+    /// Each item's slot (empty for items not shown) within `width`, and the
+    /// height of the tallest column.
+    pub(super) fn arrange(&self, width: u16, states: &[ItemState]) -> (Vec<Slot>, u16) {
+        // Units are stacked in a column: a lone item, or a row of them.
+        let mut units: Vec<Vec<usize>> = Vec::new();
+        let mut placed = vec![false; self.items.len()];
+        let names = self
+            .items
+            .iter()
+            .map(|i| i.read().unwrap().name())
+            .collect::<Vec<_>>();
+
+        for i in 0..self.items.len() {
+            if placed[i] || !states[i].is_shown() {
+                continue;
+            }
+
+            let row = self.rows.iter().find(|r| r.contains(&names[i]));
+            let unit = (i..self.items.len())
+                .filter(|j| match row {
+                    Some(row) => row.contains(&names[*j]),
+                    None => *j == i,
+                })
+                .filter(|j| states[*j].is_shown() && !placed[*j])
+                .collect::<Vec<_>>();
+
+            unit.iter().for_each(|j| placed[*j] = true);
+            units.push(unit);
+        }
+
+        let heights = units
+            .iter()
+            .map(|u| {
+                u.iter()
+                    .map(|i| self.items[*i].read().unwrap().height())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let (columns, gutter) = match self.layout {
+            FormLayout::Stacked => (1, None),
+            FormLayout::Columns(n) => (n.max(1), None),
+            FormLayout::LabelLeft(w) => (1, Some(w)),
+        };
+
+        // Compact forms leave no blank row between items.
+        let gap = match self.compact {
+            true => 0,
+            false => 1,
+        };
+
+        // Fill each column up to an even share of the total height.
+        let total = heights.iter().sum::<u16>() + units.len().saturating_sub(1) as u16 * gap;
+        let share = total.div_ceil(columns);
+        let mut column_of = Vec::with_capacity(units.len());
+        let (mut column, mut used) = (0, 0);
+        for h in &heights {
+            if used > 0 && used + h > share && column + 1 < columns {
+                column += 1;
+                used = 0;
+            }
+
+            column_of.push(column);
+            used += h + gap;
+        }
+
+        let column_areas = Layout::new(
+            Direction::Horizontal,
+            (0..columns).map(|_| Constraint::Fill(1)),
+        )
+        .spacing(2)
+        .split(Rect::new(0, 0, width, 1));
+
+        let mut slots = vec![Slot::default(); self.items.len()];
+        let mut bottoms = vec![1; columns as usize];
+        for ((unit, h), column) in units.iter().zip(&heights).zip(column_of) {
+            let c = column as usize;
+            let area = Rect {
+                y: bottoms[c],
+                height: *h,
+                ..column_areas[c]
+            };
+
+            let parts = Layout::new(
+                Direction::Horizontal,
+                unit.iter().map(|_| Constraint::Fill(1)),
+            )
+            .spacing(1)
+            .split(area);
+
+            for (i, part) in unit.iter().zip(parts.iter()) {
+                let height = self.items[*i].read().unwrap().height();
+                let part = Rect { height, ..*part };
+                let labelled = !self.items[*i].read().unwrap().label().is_empty();
+                slots[*i] = match gutter {
+                    Some(w) if labelled && part.width > w + 1 => Slot {
+                        item: Rect {
+                            x: part.x + w + 1,
+                            width: part.width - w - 1,
+                            ..part
+                        },
+                        label: Some(Rect {
+                            y: part.y + height / 2,
+                            width: w,
+                            height: 1,
+                            ..part
+                        }),
+                    },
+                    _ => Slot {
+                        item: part,
+                        label: None,
+                    },
+                };
+            }
+
+            bottoms[c] += h + gap;
+        }
+
+        let height = bottoms
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or(1)
+            .saturating_sub(1 + gap);
+        (slots, height)
+    }
+
+    /// The nearest focusable item from the focused one in `direction`, going
+    /// by where items were last drawn.
+    pub(super) fn neighbour(&self, direction: Nav) -> Option<usize> {
+        let states = self.states();
+        let viewport = self.viewport.read().unwrap();
+        let from = *viewport.rects.get(self.focused)?;
+        let centre = |r: &Rect| {
+            (
+                r.x as i32 * 2 + r.width as i32,
+                r.y as i32 * 2 + r.height as i32,
+            )
+        };
+        let overlaps_x = |r: &Rect| r.x < from.right() && from.x < r.right();
+        let (fx, fy) = centre(&from);
+
+        viewport
+            .rects
+            .iter()
+            .enumerate()
+            .filter(|(i, r)| {
+                *i != self.focused && states.get(*i).is_some_and(|s| s.can_focus()) && !r.is_empty()
+            })
+            .filter(|(_, r)| match direction {
+                Nav::Up => r.bottom() <= from.y && overlaps_x(r),
+                Nav::Down => r.y >= from.bottom() && overlaps_x(r),
+                Nav::Left => r.right() <= from.x,
+                Nav::Right => r.x >= from.right(),
+            })
+            .min_by_key(|(_, r)| {
+                let (x, y) = centre(r);
+                match direction {
+                    Nav::Up | Nav::Down => ((y - fy).abs(), (x - fx).abs()),
+                    Nav::Left | Nav::Right => ((x - fx).abs(), (y - fy).abs()),
+                }
+            })
+            .map(|(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::buffer::Buffer;
+
+    use super::*;
+    use crate::widgets::{Button, Input};
+
+    fn focused(form: &Form) -> String {
+        form.items[form.focused].read().unwrap().name()
+    }
+
+    #[test]
+    fn editing_items_need_alt_to_change_columns() {
+        let mut form = Form::new();
+        form.push(Input::new("first", "First", "ab"));
+        form.push(Input::new("last", "Last", ""));
+        form.push(Button::new("save", "Save", |_| {}));
+        form.push(Button::new("cancel", "Cancel", |_| {}));
+        form.set_layout(FormLayout::Columns(2));
+        form.focus_by_name("first");
+        let mut buf = Buffer::empty(Rect::new(0, 0, 60, 12));
+        form.ren(buf.area, &mut buf);
+
+        form.handle_input(KeyCode::Left);
+        assert_eq!(focused(&form), "first");
+
+        form.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::ALT));
+        assert_eq!(focused(&form), "save");
+
+        form.handle_input(KeyCode::Left);
+        assert_eq!(focused(&form), "first");
+    }
+}
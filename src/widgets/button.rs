@@ -5,32 +5,85 @@ use ratatui::{
     layout::{Alignment, Rect},
     style::{Style, Stylize},
     text::Line,
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget},
 };
 
-use super::{Form, FormItem};
+use super::{render_compact_label, Form, FormItem, Theme};
 use crate::ab;
 
+pub type ButtonHandler<'a> = Arc<Box<dyn Fn(&Form) -> Result<(), String> + Send + Sync + 'a>>;
+
+/// What a [`Button`] handler may return: `()`, or a `Result` whose error is
+/// shown in the form.
+pub trait HandlerResult {
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl HandlerResult for () {
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: ToString> HandlerResult for Result<(), E> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
 #[derive(Clone)]
 pub struct Button<'a> {
     pub text: String,
     pub focused: bool,
-    pub handler: Arc<Box<dyn Fn(&Form) + Send + Sync + 'a>>,
+    pub handler: ButtonHandler<'a>,
     pub name: String,
+    /// Action buttons skip validation and report [`FormOutcome::Action`]
+    /// instead of submitting.
+    ///
+    /// [`FormOutcome::Action`]: super::FormOutcome::Action
+    pub action: bool,
+    compact: bool,
+    theme: Theme,
 }
 
 impl<'a> Button<'a> {
-    pub fn new(name: &str, text: &str, handler: impl Fn(&Form) + Send + Sync + 'a) -> Self {
+    pub fn new<R: HandlerResult>(
+        name: &str,
+        text: &str,
+        handler: impl Fn(&Form) -> R + Send + Sync + 'a,
+    ) -> Self {
         Self {
             text: text.to_string(),
             focused: false,
-            handler: ab!(handler),
+            handler: ab!(move |f: &Form| handler(f).into_result()),
             name: name.to_string(),
+            action: false,
+            compact: false,
+            theme: Theme::default(),
+        }
+    }
+
+    pub fn action<R: HandlerResult>(
+        name: &str,
+        text: &str,
+        handler: impl Fn(&Form) -> R + Send + Sync + 'a,
+    ) -> Self {
+        Self {
+            action: true,
+            ..Self::new(name, text, handler)
+        }
+    }
+
+    /// Draws on one row as `[ text ]`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
         }
     }
 }
 
-impl FormItem for Button<'_> {
+impl FormItem for Button<'static> {
     fn focus(&mut self) {
         self.focused = true;
     }
@@ -43,14 +96,32 @@ impl FormItem for Button<'_> {
         self.clone().render(a, b);
     }
 
-    fn submit(&self, f: &Form) -> bool {
-        (self.handler)(f);
-        true
+    fn submit(&self, f: &Form) -> Option<Result<(), String>> {
+        Some((self.handler)(f))
+    }
+
+    fn is_submit(&self) -> bool {
+        !self.action
     }
 
     fn name(&self) -> String {
         self.name.clone()
     }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
 }
 
 impl Widget for Button<'_> {
@@ -58,16 +129,20 @@ impl Widget for Button<'_> {
     where
         Self: Sized,
     {
+        if self.compact {
+            let area = render_compact_label(area, buf, "", self.focused, &self.theme);
+            let style = match self.focused {
+                true => self.theme.selection,
+                false => self.theme.title.bold(),
+            };
+
+            Line::styled(format!("[ {} ]", self.text), style).render(area, buf);
+            return;
+        }
+
         Paragraph::new(Line::from(self.text.clone()).style(Style::new().bold()))
             .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(match self.focused {
-                        true => ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
-                        false => ratatui::style::Style::default(),
-                    }),
-            )
+            .block(self.theme.block(String::new(), self.focused))
             .render(area, buf);
     }
 }
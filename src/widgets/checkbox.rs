@@ -0,0 +1,284 @@
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{render_compact_label, shown_title, FormItem, FormValue, FromFormValue, Theme};
+
+/// How a [`Checkbox`] shows its state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CheckboxStyle {
+    /// `[x]`, `[ ]`, or `[-]` for "inherit".
+    #[default]
+    Box,
+    /// `Off On`, with the current position highlighted.
+    Switch,
+}
+
+/// A yes / no box, flipped with Space, Enter or a click. [`Checkbox::toggle`]
+/// makes one drawn as an on / off switch.
+#[derive(Clone)]
+pub struct Checkbox {
+    pub name: String,
+    pub title: String,
+    /// `None` is the "inherit" state of a [tri-state](Self::tri_state) box.
+    pub value: Option<bool>,
+    pub focused: bool,
+    pub tri_state: bool,
+    pub style: CheckboxStyle,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
+    initial: Option<bool>,
+}
+
+impl Checkbox {
+    pub fn new(name: &str, title: &str, value: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            value: Some(value),
+            focused: false,
+            tri_state: false,
+            style: CheckboxStyle::Box,
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
+            initial: Some(value),
+        }
+    }
+
+    /// An on / off switch, read and set like any other checkbox.
+    pub fn toggle(name: &str, title: &str, value: bool) -> Self {
+        Self {
+            style: CheckboxStyle::Switch,
+            ..Self::new(name, title, value)
+        }
+    }
+
+    /// Adds an "inherit" state after checked, which reads as `Null`.
+    pub fn tri_state(self) -> Self {
+        Self {
+            tri_state: true,
+            ..self
+        }
+    }
+
+    /// Starts out (and resets to) "inherit". Implies [`tri_state`](Self::tri_state).
+    pub fn inherit(self) -> Self {
+        Self {
+            tri_state: true,
+            value: None,
+            initial: None,
+            ..self
+        }
+    }
+
+    /// Draws on one row as `Title: [x]` (or `Title: Off On`).
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
+        }
+    }
+
+    /// Unchecked, checked, then "inherit" if tri-state.
+    pub fn flip(&mut self) {
+        self.value = match self.value {
+            Some(false) => Some(true),
+            Some(true) if self.tri_state => None,
+            _ => Some(false),
+        };
+    }
+
+    fn mark(&self) -> Span<'static> {
+        match self.value {
+            Some(true) => Span::styled("[x]", self.theme.selection),
+            Some(false) => Span::raw("[ ]"),
+            None => Span::styled("[-]", self.theme.muted),
+        }
+    }
+
+    /// Each position, with the current one highlighted.
+    fn switch(&self) -> Line<'static> {
+        let mut positions = vec![("Off", Some(false)), ("On", Some(true))];
+        if self.tri_state {
+            positions.push(("Inherit", None));
+        }
+
+        let spans = positions
+            .into_iter()
+            .map(
+                |(label, value)| match (self.value == value, self.theme.monochrome) {
+                    (true, true) => Span::styled(format!("[{label}]"), self.theme.selection),
+                    (true, false) => Span::styled(format!(" {label} "), self.theme.selection),
+                    (false, _) => Span::styled(format!(" {label} "), self.theme.muted),
+                },
+            )
+            .collect::<Vec<_>>();
+
+        Line::from(spans)
+    }
+
+    /// The state as drawn in the box or on the compact row.
+    fn state(&self, compact: bool) -> Line<'static> {
+        match (self.style, self.value) {
+            (CheckboxStyle::Switch, _) => self.switch(),
+            (CheckboxStyle::Box, None) if !compact => Line::from(vec![
+                self.mark(),
+                Span::styled(" Inherit", self.theme.muted),
+            ]),
+            (CheckboxStyle::Box, _) => self.mark().into(),
+        }
+    }
+}
+
+impl FormItem for Checkbox {
+    fn ren(&self, a: Rect, b: &mut Buffer) {
+        self.clone().render(a, b);
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
+
+    fn input(&mut self, k: KeyEvent) {
+        if matches!(k.code, KeyCode::Char(' ') | KeyCode::Enter) {
+            self.flip();
+        }
+    }
+
+    fn mouse(&mut self, m: MouseEvent, _: Rect) {
+        if m.kind == MouseEventKind::Down(MouseButton::Left) {
+            self.flip();
+        }
+    }
+
+    /// `Bool`, or `Null` when "inherit".
+    fn value(&self) -> FormValue {
+        self.value.map(FormValue::Bool).unwrap_or(FormValue::Null)
+    }
+
+    /// `Null` clears a tri-state box; anything else goes through
+    /// [`FromFormValue`] for `bool`, so "yes" / "no" text works too.
+    fn set_value(&mut self, v: FormValue) -> bool {
+        self.value = match v {
+            FormValue::Null if self.tri_state => None,
+            v => match bool::from_form_value(&v) {
+                Ok(value) => Some(value),
+                Err(_) => return false,
+            },
+        };
+
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = self.initial;
+    }
+}
+
+impl Widget for Checkbox {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let label = shown_title(self.show_title, &self.title);
+
+        if self.compact {
+            let area = render_compact_label(area, buf, label, self.focused, &self.theme);
+            self.state(true).render(area, buf);
+            return;
+        }
+
+        Paragraph::new(self.state(false))
+            .block(self.theme.block(label.to_string(), self.focused))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What's inside the border.
+    fn row(item: &Checkbox) -> String {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 3));
+        item.ren(buf.area, &mut buf);
+        (1..buf.area.width - 1)
+            .map(|x| buf.get(x, 1).symbol())
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+
+    #[test]
+    fn boxes_and_toggles_only_differ_in_drawing() {
+        let mut checkbox = Checkbox::new("tls", "TLS", false).tri_state();
+        let mut toggle = Checkbox::toggle("tls", "TLS", false).tri_state();
+        checkbox.set_theme(&Theme::monochrome());
+        toggle.set_theme(&Theme::monochrome());
+
+        for (set, value, mark, switch) in [
+            (
+                FormValue::Bool(true),
+                Some(true),
+                "[x]",
+                " Off [On] Inherit",
+            ),
+            (FormValue::Null, None, "[-] Inherit", " Off  On [Inherit]"),
+            ("no".into(), Some(false), "[ ]", "[Off] On  Inherit"),
+        ] {
+            assert!(checkbox.set_value(set.clone()));
+            assert!(toggle.set_value(set));
+            assert_eq!(checkbox.value, value);
+            assert_eq!(toggle.value, value);
+            assert_eq!(row(&checkbox), mark);
+            assert_eq!(row(&toggle), switch);
+        }
+
+        checkbox.input(KeyCode::Char(' ').into());
+        toggle.input(KeyCode::Char(' ').into());
+        assert_eq!(checkbox.value(), FormValue::Bool(true));
+        assert_eq!(toggle.value(), FormValue::Bool(true));
+    }
+
+    #[test]
+    fn two_state_boxes_reject_null() {
+        let mut toggle = Checkbox::toggle("tls", "TLS", true);
+        assert!(!toggle.set_value(FormValue::Null));
+        assert!(!toggle.set_value(FormValue::Text("maybe".to_string())));
+        assert_eq!(toggle.value(), FormValue::Bool(true));
+    }
+}
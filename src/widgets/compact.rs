@@ -0,0 +1,91 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Rect},
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{Form, Theme};
+
+/// Drawn before the focused item in compact mode, in place of a border.
+pub const FOCUS_MARKER: &str = "> ";
+
+/// Splits a compact item's row into its `> Label: ` lead-in and the rest,
+/// where the value goes. An empty label leaves just the marker.
+pub fn compact_split(area: Rect, label: &str) -> (Rect, Rect) {
+    let lead = match label.is_empty() {
+        true => FOCUS_MARKER.width(),
+        false => FOCUS_MARKER.width() + label.width() + 2,
+    } as u16;
+
+    let lead = lead.min(area.width);
+    (
+        Rect {
+            width: lead,
+            height: 1,
+            ..area
+        },
+        Rect {
+            x: area.x + lead,
+            width: area.width - lead,
+            height: 1,
+            ..area
+        },
+    )
+}
+
+/// Where a text-like item draws its value: after the lead-in when compact,
+/// otherwise inside its border.
+pub fn value_area(area: Rect, label: &str, compact: bool) -> Rect {
+    match compact {
+        true => compact_split(area, label).1,
+        false => area.inner(Margin::new(1, 1)),
+    }
+}
+
+/// `title`, or nothing while the form draws labels itself. See
+/// [`FormItem::set_show_title`](super::FormItem::set_show_title).
+pub fn shown_title(show: bool, title: &str) -> &str {
+    match show {
+        true => title,
+        false => "",
+    }
+}
+
+/// Draws a compact item's lead-in, returning the area left for its value.
+pub fn render_compact_label(
+    area: Rect,
+    buf: &mut Buffer,
+    label: &str,
+    focused: bool,
+    theme: &Theme,
+) -> Rect {
+    let (lead, value) = compact_split(area, label);
+    let style = match focused {
+        true => theme.selection,
+        false => theme.title,
+    };
+
+    let marker = match focused {
+        true => FOCUS_MARKER.to_string(),
+        false => " ".repeat(FOCUS_MARKER.width()),
+    };
+
+    let text = match label.is_empty() {
+        true => marker,
+        false => format!("{marker}{label}: "),
+    };
+
+    buf.set_stringn(lead.x, lead.y, text, lead.width as usize, style);
+    value
+}
+
+impl Form {
+    /// Draws every item, including ones added later, on a single row.
+    /// Turning it off also undoes items built with `compact()`.
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+        for item in &self.items {
+            item.write().unwrap().set_compact(compact);
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::{collections::HashMap, fmt::Display, sync::Arc};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{render_compact_label, shown_title, Form, FormItem, FormValue, Theme};
+
+/// A read-only item showing a value worked out from other items. Attach the
+/// calculation with [`Form::compute`].
+#[derive(Clone)]
+pub struct Computed {
+    pub name: String,
+    pub title: String,
+    pub value: FormValue,
+    pub focused: bool,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
+}
+
+impl Computed {
+    pub fn new(name: &str, title: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            value: FormValue::Null,
+            focused: false,
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Draws on one row as `Title: value`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
+        }
+    }
+}
+
+impl FormItem for Computed {
+    fn ren(&self, a: Rect, b: &mut Buffer) {
+        self.clone().render(a, b);
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+
+    fn value(&self) -> FormValue {
+        self.value.clone()
+    }
+
+    fn set_value(&mut self, v: FormValue) -> bool {
+        self.value = v;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = FormValue::Null;
+    }
+}
+
+impl Widget for Computed {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
+
+            let area = render_compact_label(area, buf, label, self.focused, &self.theme);
+            Paragraph::new(self.value.to_string())
+                .style(Style::new().add_modifier(Modifier::ITALIC))
+                .render(area, buf);
+            return;
+        }
+
+        Paragraph::new(self.value.to_string())
+            .style(Style::new().add_modifier(Modifier::ITALIC))
+            .block(
+                self.theme
+                    .block(shown_title(self.show_title, &self.title).to_string(), false),
+            )
+            .render(area, buf);
+    }
+}
+
+pub type ComputeFn = Arc<dyn Fn(&HashMap<String, FormValue>) -> FormValue + Send + Sync>;
+
+#[derive(Clone)]
+pub(super) struct Computation {
+    name: String,
+    deps: Vec<String>,
+    f: ComputeFn,
+}
+
+/// Returned by [`Form::compute`] when the new calculation would depend on
+/// its own result, with the names along the loop.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleError(pub Vec<String>);
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "computed fields form a cycle: {}", self.0.join(" -> "))
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+impl Form {
+    /// Sets the item named `name` (usually a [`Computed`]) to `f` of the
+    /// items named in `deps`, now and whenever one of them changes.
+    ///
+    /// `f` only sees the values of `deps`. Fails, leaving the form as it was,
+    /// if `name` would end up depending on itself.
+    pub fn compute(
+        &mut self,
+        name: &str,
+        deps: &[&str],
+        f: impl Fn(&HashMap<String, FormValue>) -> FormValue + Send + Sync + 'static,
+    ) -> Result<(), CycleError> {
+        let mut computations = self.computations.clone();
+        computations.retain(|c| c.name != name);
+        computations.push(Computation {
+            name: name.to_string(),
+            deps: deps.iter().map(|d| d.to_string()).collect(),
+            f: Arc::new(f),
+        });
+
+        self.computations = sort(computations)?;
+        self.recompute();
+        Ok(())
+    }
+
+    /// Drops the calculation that sets the item named `name`, if any.
+    pub(super) fn forget_computation(&mut self, name: &str) {
+        self.computations.retain(|c| c.name != name);
+    }
+
+    /// Re-runs every calculation, in dependency order.
+    pub(super) fn recompute(&mut self) {
+        for c in &self.computations {
+            let values = c
+                .deps
+                .iter()
+                .filter_map(|d| {
+                    let i = self.index_of(d)?;
+                    Some((d.clone(), self.items[i].read().unwrap().value()))
+                })
+                .collect::<HashMap<_, _>>();
+
+            let Some(i) = self.index_of(&c.name) else {
+                continue;
+            };
+
+            let value = (c.f)(&values);
+            let mut item = self.items[i].write().unwrap();
+            if item.value() != value {
+                item.set_value(value);
+            }
+        }
+    }
+}
+
+/// Orders `computations` so each runs after the ones it depends on.
+fn sort(computations: Vec<Computation>) -> Result<Vec<Computation>, CycleError> {
+    fn visit(
+        i: usize,
+        computations: &[Computation],
+        path: &mut Vec<usize>,
+        done: &mut Vec<bool>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), CycleError> {
+        if done[i] {
+            return Ok(());
+        }
+
+        if let Some(start) = path.iter().position(|p| *p == i) {
+            let mut names = path[start..]
+                .iter()
+                .map(|p| computations[*p].name.clone())
+                .collect::<Vec<_>>();
+            names.push(computations[i].name.clone());
+            return Err(CycleError(names));
+        }
+
+        path.push(i);
+        for dep in &computations[i].deps {
+            if let Some(d) = computations.iter().position(|c| c.name == *dep) {
+                visit(d, computations, path, done, order)?;
+            }
+        }
+
+        path.pop();
+        done[i] = true;
+        order.push(i);
+        Ok(())
+    }
+
+    let mut done = vec![false; computations.len()];
+    let mut order = Vec::new();
+    for i in 0..computations.len() {
+        visit(i, &computations, &mut Vec::new(), &mut done, &mut order)?;
+    }
+
+    Ok(order.into_iter().map(|i| computations[i].clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::NumInput;
+
+    fn int(values: &HashMap<String, FormValue>, name: &str) -> i64 {
+        values
+            .get(name)
+            .and_then(FormValue::as_i64)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn an_item_cant_depend_on_itself() {
+        let mut form = Form::new();
+        form.push(Computed::new("a", "A"));
+
+        let result = form.compute("a", &["a"], |_| FormValue::Null);
+        assert_eq!(
+            result,
+            Err(CycleError(vec!["a".to_string(), "a".to_string()]))
+        );
+        assert!(form.computations.is_empty());
+    }
+
+    #[test]
+    fn indirect_cycles_are_rejected_and_change_nothing() {
+        let mut form = Form::new();
+        form.push(NumInput::new("n", "N", 1, (0, 100), 1));
+        form.push(Computed::new("a", "A"));
+        form.push(Computed::new("b", "B"));
+        form.compute("a", &["b", "n"], |v| (int(v, "b") + int(v, "n")).into())
+            .unwrap();
+
+        let result = form.compute("b", &["a"], |v| (int(v, "a") * 2).into());
+        let Err(e) = result else {
+            panic!("a -> b -> a was accepted");
+        };
+        assert_eq!(e.0, ["a", "b", "a"]);
+        assert_eq!(e.to_string(), "computed fields form a cycle: a -> b -> a");
+
+        assert_eq!(form.computations.len(), 1);
+        assert_eq!(form.values()["a"], FormValue::Int(1));
+        assert_eq!(form.values()["b"], FormValue::Null);
+    }
+
+    #[test]
+    fn computed_values_update_after_the_ones_they_use() {
+        let mut form = Form::new();
+        form.push(NumInput::new("price", "Price", 5, (0, 100), 1));
+        form.push(NumInput::new("qty", "Quantity", 2, (0, 100), 1));
+        form.push(Computed::new("total", "Total"));
+        form.push(Computed::new("subtotal", "Subtotal"));
+
+        // Registered before what it depends on, and drawn before it too.
+        form.compute("total", &["subtotal"], |v| (int(v, "subtotal") + 1).into())
+            .unwrap();
+        form.compute("subtotal", &["price", "qty"], |v| {
+            (int(v, "price") * int(v, "qty")).into()
+        })
+        .unwrap();
+        assert_eq!(form.values()["total"], FormValue::Int(11));
+
+        form.fill([("qty", 3)]);
+        assert_eq!(form.values()["subtotal"], FormValue::Int(15));
+        assert_eq!(form.values()["total"], FormValue::Int(16));
+    }
+}
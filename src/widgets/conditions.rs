@@ -0,0 +1,145 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::{Form, FormValue, Section};
+
+/// Decides from every item's value (hidden ones included) whether an item is
+/// shown or editable. See [`Form::visible_when`] and [`Form::enabled_when`].
+pub type Condition = Arc<dyn Fn(&HashMap<String, FormValue>) -> bool + Send + Sync>;
+
+/// Holds when the item named `name` currently has `value`, e.g. a [`Radio`]
+/// option or a number typed into a [`NumInput`].
+///
+/// [`Radio`]: super::Radio
+/// [`NumInput`]: super::NumInput
+pub fn field_is(
+    name: &str,
+    value: &str,
+) -> impl Fn(&HashMap<String, FormValue>) -> bool + Send + Sync {
+    let (name, value) = (name.to_string(), value.to_string());
+    move |values: &HashMap<String, FormValue>| {
+        values.get(&name).is_some_and(|v| v.to_string() == value)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(super) struct ItemState {
+    pub visible: bool,
+    pub enabled: bool,
+    pub read_only: bool,
+    /// Under a collapsed [`Section`](super::Section).
+    pub collapsed: bool,
+}
+
+impl ItemState {
+    /// Whether the item takes up space in the layout.
+    pub fn is_shown(&self) -> bool {
+        self.visible && !self.collapsed
+    }
+
+    /// Whether the item is shown and editable, so its errors count.
+    pub fn is_active(&self) -> bool {
+        self.visible && self.enabled
+    }
+
+    /// Whether the item can take focus and input.
+    pub fn can_focus(&self) -> bool {
+        self.is_active() && !self.read_only && !self.collapsed
+    }
+}
+
+impl Form {
+    /// Only shows the item named `name` while `f` holds. Hidden items are
+    /// skipped by navigation, left out of [`values`](Self::values) and
+    /// [`slurp`](Self::slurp), and not validated.
+    pub fn visible_when(
+        &mut self,
+        name: &str,
+        f: impl Fn(&HashMap<String, FormValue>) -> bool + Send + Sync + 'static,
+    ) {
+        self.visible_when.insert(name.to_string(), Arc::new(f));
+    }
+
+    /// Only lets the item named `name` be focused or edited while `f` holds.
+    /// Disabled items are drawn dimmed, keep their value, and aren't
+    /// validated.
+    pub fn enabled_when(
+        &mut self,
+        name: &str,
+        f: impl Fn(&HashMap<String, FormValue>) -> bool + Send + Sync + 'static,
+    ) {
+        self.enabled_when.insert(name.to_string(), Arc::new(f));
+    }
+
+    pub fn is_visible(&self, name: &str) -> bool {
+        self.index_of(name)
+            .is_some_and(|i| self.states()[i].visible)
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.index_of(name)
+            .is_some_and(|i| self.states()[i].enabled)
+    }
+
+    pub(super) fn states(&self) -> Vec<ItemState> {
+        let values = match self.visible_when.is_empty() && self.enabled_when.is_empty() {
+            true => HashMap::new(),
+            false => self.all_values(),
+        };
+
+        let mut states = self
+            .items
+            .iter()
+            .map(|i| {
+                let i = i.read().unwrap();
+                let name = i.name();
+                let holds = |c: Option<&Condition>| c.is_none_or(|c| c(&values));
+                ItemState {
+                    visible: holds(self.visible_when.get(&name)),
+                    enabled: holds(self.enabled_when.get(&name)),
+                    read_only: i.is_read_only(),
+                    collapsed: false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Items follow their section header: hidden with it, and folded away
+        // while it's collapsed.
+        for (i, section) in self.sections().into_iter().enumerate() {
+            let Some(s) = section else {
+                continue;
+            };
+
+            let header = self.items[s].read().unwrap();
+            let header = (**header).as_any().downcast_ref::<Section>().unwrap();
+            states[i].visible &= states[s].visible;
+            states[i].collapsed = header.collapsed;
+        }
+
+        states
+    }
+
+    /// Moves focus to the next (or previous) item that can take it, wrapping
+    /// around.
+    pub(super) fn step_focus(&mut self, forward: bool) {
+        let states = self.states();
+        let len = self.items.len();
+        let next = (1..=len)
+            .map(|n| match forward {
+                true => (self.focused + n) % len,
+                false => (self.focused + len - n % len) % len,
+            })
+            .find(|&i| states[i].can_focus());
+
+        if let Some(i) = next {
+            self.move_focus(i);
+        }
+    }
+
+    /// Moves focus off the focused item if it has just been hidden or
+    /// disabled, or is read-only.
+    pub(super) fn settle_focus(&mut self) {
+        if !self.items.is_empty() && !self.states()[self.focused].can_focus() {
+            self.step_focus(true);
+        }
+    }
+}
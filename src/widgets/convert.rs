@@ -0,0 +1,159 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Form, FormValue};
+
+/// A struct that can be edited with a [`Form`], usually via
+/// `#[derive(TuwiForm)]` (with the `derive` feature).
+pub trait TuwiForm: Sized {
+    /// Builds a form prefilled with this value's fields, failing on a field
+    /// its widget can't hold (e.g. a `u64` above `i64::MAX`).
+    fn try_to_form(&self) -> Result<Form, FieldError>;
+
+    /// Like [`try_to_form`](Self::try_to_form), but panics on a field its
+    /// widget can't hold.
+    fn to_form(&self) -> Form {
+        self.try_to_form().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Reads the struct back out of a (possibly edited) form.
+    fn from_form(form: &Form) -> Result<Self, FieldError>;
+
+    fn form() -> Form
+    where
+        Self: Default,
+    {
+        Self::default().to_form()
+    }
+}
+
+/// A form item whose value couldn't be converted into its field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// Converts an integer field into the type its widget holds, for
+/// `#[derive(TuwiForm)]`.
+#[doc(hidden)]
+pub fn int_field<T: TryFrom<F>, F: Copy + Display>(name: &str, value: F) -> Result<T, FieldError> {
+    T::try_from(value).map_err(|_| FieldError {
+        field: name.to_string(),
+        message: format!("{value} is out of range"),
+    })
+}
+
+/// Conversion from a [`FormValue`] into a struct field's type.
+pub trait FromFormValue: Sized {
+    fn from_form_value(value: &FormValue) -> Result<Self, String>;
+
+    /// Converts the value of the item named `name` in `values`.
+    fn from_field(values: &HashMap<String, FormValue>, name: &str) -> Result<Self, FieldError> {
+        Self::from_form_value(values.get(name).unwrap_or(&FormValue::Null)).map_err(|message| {
+            FieldError {
+                field: name.to_string(),
+                message,
+            }
+        })
+    }
+}
+
+impl FromFormValue for String {
+    fn from_form_value(value: &FormValue) -> Result<Self, String> {
+        match value {
+            FormValue::Null => Err("missing value".to_string()),
+            v => Ok(v.to_string()),
+        }
+    }
+}
+
+macro_rules! from_form_value_num {
+    ($($ty:ty),*) => {
+        $(
+            impl FromFormValue for $ty {
+                fn from_form_value(value: &FormValue) -> Result<Self, String> {
+                    match value {
+                        FormValue::Int(i) => <$ty>::try_from(*i).map_err(|_| format!("{i} is out of range")),
+                        FormValue::Text(s) | FormValue::Choice(s) => s
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("expected an integer, found `{s}`")),
+                        v => Err(format!("expected an integer, found `{v}`")),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_form_value_num!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! from_form_value_float {
+    ($($ty:ty),*) => {
+        $(
+            impl FromFormValue for $ty {
+                fn from_form_value(value: &FormValue) -> Result<Self, String> {
+                    match value {
+                        FormValue::Int(i) => Ok(*i as $ty),
+                        FormValue::Float(f) => Ok(*f as $ty),
+                        FormValue::Text(s) | FormValue::Choice(s) => s
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("expected a number, found `{s}`")),
+                        v => Err(format!("expected a number, found `{v}`")),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_form_value_float!(f32, f64);
+
+impl FromFormValue for bool {
+    fn from_form_value(value: &FormValue) -> Result<Self, String> {
+        match value {
+            FormValue::Bool(b) => Ok(*b),
+            FormValue::Text(s) | FormValue::Choice(s) => match s.trim() {
+                "true" | "yes" => Ok(true),
+                "false" | "no" => Ok(false),
+                s => Err(format!("expected yes or no, found `{s}`")),
+            },
+            v => Err(format!("expected yes or no, found `{v}`")),
+        }
+    }
+}
+
+impl FromFormValue for Vec<String> {
+    fn from_form_value(value: &FormValue) -> Result<Self, String> {
+        match value {
+            FormValue::List(l) => Ok(l.clone()),
+            FormValue::Null => Ok(vec![]),
+            v => Ok(v
+                .to_string()
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()),
+        }
+    }
+}
+
+/// `Null` and empty text are `None`.
+impl<T: FromFormValue> FromFormValue for Option<T> {
+    fn from_form_value(value: &FormValue) -> Result<Self, String> {
+        match value {
+            FormValue::Null => Ok(None),
+            FormValue::Text(s) if s.is_empty() => Ok(None),
+            v => T::from_form_value(v).map(Some),
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{
+    de::{
+        self, value::StrDeserializer, DeserializeOwned, IntoDeserializer, MapAccess, SeqAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use super::{Form, FormValue};
+
+/// Why [`Form::deserialize`] failed, and on which item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeserializeError {
+    /// Dotted item name, e.g. `server.port`. Empty for the form itself.
+    pub field: String,
+    pub message: String,
+}
+
+impl DeserializeError {
+    fn at(mut self, field: &str) -> Self {
+        if self.field.is_empty() {
+            self.field = field.to_string();
+        }
+
+        self
+    }
+}
+
+impl Display for DeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.field.is_empty() {
+            true => f.write_str(&self.message),
+            false => write!(f, "`{}`: {}", self.field, self.message),
+        }
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self {
+            field: String::new(),
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Item values arranged by their dotted names, so `server.port` becomes a
+/// `port` field of a nested `server` struct.
+enum Node {
+    Value(FormValue),
+    Map(Vec<(String, Node)>),
+}
+
+impl Node {
+    /// Fails if `name` (e.g. `server`) would be both a value and a group of
+    /// fields (`server.port`), or is used twice.
+    fn insert(&mut self, name: &str, value: FormValue) -> Result<(), DeserializeError> {
+        let clash = |message: &str| DeserializeError {
+            field: name.to_string(),
+            message: message.to_string(),
+        };
+
+        let mut node = self;
+        for head in name.split('.') {
+            let Node::Map(entries) = node else {
+                return Err(clash("is nested under an item that holds a value"));
+            };
+
+            let i = match entries.iter().position(|(k, _)| k == head) {
+                Some(i) => i,
+                None => {
+                    entries.push((head.to_string(), Node::Map(vec![])));
+                    entries.len() - 1
+                }
+            };
+
+            node = &mut entries[i].1;
+        }
+
+        match node {
+            Node::Value(_) => Err(clash("is the name of more than one item")),
+            Node::Map(entries) if !entries.is_empty() => {
+                Err(clash("holds a value but also has nested items"))
+            }
+            _ => {
+                *node = Node::Value(value);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Form {
+    /// Builds `T` from the same values as [`values`](Self::values),
+    /// matching item names to field names. Dotted names (`server.port`) fill
+    /// nested structs.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, DeserializeError> {
+        let mut root = Node::Map(vec![]);
+        for (name, value) in self.visible_values(self.section_prefix) {
+            root.insert(&name, value)?;
+        }
+
+        T::deserialize(NodeDeserializer {
+            node: &root,
+            path: String::new(),
+        })
+    }
+}
+
+struct NodeDeserializer<'a> {
+    node: &'a Node,
+    path: String,
+}
+
+impl NodeDeserializer<'_> {
+    fn text(&self) -> Option<&str> {
+        match self.node {
+            Node::Value(v) => v.as_str(),
+            Node::Map(_) => None,
+        }
+    }
+
+    fn parse<T: std::str::FromStr>(&self, s: &str, expected: &str) -> Result<T, DeserializeError> {
+        s.trim().parse().map_err(|_| DeserializeError {
+            field: self.path.clone(),
+            message: format!("expected {expected}, found `{s}`"),
+        })
+    }
+}
+
+macro_rules! parse_from_text {
+    ($($method:ident => $visit:ident: $ty:ty, $expected:literal;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.text() {
+                    Some(s) => visitor.$visit(self.parse::<$ty>(s, $expected)?),
+                    None => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer<'_> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let path = self.path.clone();
+        match self.node {
+            Node::Map(entries) => visitor.visit_map(NodeMap {
+                entries: entries.iter(),
+                value: None,
+                path: self.path,
+            }),
+            Node::Value(FormValue::Text(s) | FormValue::Choice(s)) => visitor.visit_str(s),
+            Node::Value(FormValue::Int(i)) => visitor.visit_i64(*i),
+            Node::Value(FormValue::Float(f)) => visitor.visit_f64(*f),
+            Node::Value(FormValue::Bool(b)) => visitor.visit_bool(*b),
+            Node::Value(FormValue::List(l)) => visitor.visit_seq(ListSeq {
+                items: l.iter(),
+                path: self.path,
+            }),
+            Node::Value(FormValue::Records(r)) => visitor.visit_seq(RecordSeq {
+                records: r.iter().enumerate(),
+                path: self.path,
+            }),
+            Node::Value(FormValue::Null) => visitor.visit_unit(),
+        }
+        .map_err(|e: DeserializeError| e.at(&path))
+    }
+
+    parse_from_text! {
+        deserialize_i8 => visit_i8: i8, "an integer";
+        deserialize_i16 => visit_i16: i16, "an integer";
+        deserialize_i32 => visit_i32: i32, "an integer";
+        deserialize_i64 => visit_i64: i64, "an integer";
+        deserialize_u8 => visit_u8: u8, "a positive integer";
+        deserialize_u16 => visit_u16: u16, "a positive integer";
+        deserialize_u32 => visit_u32: u32, "a positive integer";
+        deserialize_u64 => visit_u64: u64, "a positive integer";
+        deserialize_f32 => visit_f32: f32, "a number";
+        deserialize_f64 => visit_f64: f64, "a number";
+        deserialize_bool => visit_bool: bool, "`true` or `false`";
+        deserialize_char => visit_char: char, "a single character";
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    /// Numbers and booleans are accepted as their string form.
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.node {
+            Node::Value(v @ (FormValue::Int(_) | FormValue::Float(_) | FormValue::Bool(_))) => {
+                visitor.visit_string(v.to_string())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    /// `Null` and empty text are `None`.
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.node {
+            Node::Value(FormValue::Null) => visitor.visit_none(),
+            Node::Value(FormValue::Text(s)) if s.is_empty() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are picked by the text or choice value.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.text() {
+            Some(s) => {
+                let s: StrDeserializer<DeserializeError> = s.into_deserializer();
+                visitor
+                    .visit_enum(s)
+                    .map_err(|e: DeserializeError| e.at(&self.path))
+            }
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct NodeMap<'a, I: Iterator<Item = &'a (String, Node)>> {
+    entries: I,
+    value: Option<&'a (String, Node)>,
+    path: String,
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a (String, Node)>> MapAccess<'de> for NodeMap<'a, I> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(entry) = self.entries.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some(entry);
+        seed.deserialize(entry.0.as_str().into_deserializer())
+            .map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, node) = self.value.take().expect("value requested before key");
+        let path = match self.path.is_empty() {
+            true => key.clone(),
+            false => format!("{}.{key}", self.path),
+        };
+
+        seed.deserialize(NodeDeserializer {
+            node,
+            path: path.clone(),
+        })
+        .map_err(|e| e.at(&path))
+    }
+}
+
+struct ListSeq<'a, I: Iterator<Item = &'a String>> {
+    items: I,
+    path: String,
+}
+
+impl<'de, 'a, I: Iterator<Item = &'a String>> SeqAccess<'de> for ListSeq<'a, I> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some(item) = self.items.next() else {
+            return Ok(None);
+        };
+
+        let node = Node::Value(FormValue::Text(item.clone()));
+        seed.deserialize(NodeDeserializer {
+            node: &node,
+            path: self.path.clone(),
+        })
+        .map(Some)
+    }
+}
+
+/// [`Group`](super::Group) entries, each read like a form of its own.
+struct RecordSeq<'a, I: Iterator<Item = (usize, &'a HashMap<String, FormValue>)>> {
+    records: I,
+    path: String,
+}
+
+impl<'de, 'a, I> SeqAccess<'de> for RecordSeq<'a, I>
+where
+    I: Iterator<Item = (usize, &'a HashMap<String, FormValue>)>,
+{
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((i, record)) = self.records.next() else {
+            return Ok(None);
+        };
+
+        let path = format!("{}[{i}]", self.path);
+        let mut node = Node::Map(vec![]);
+        for (name, value) in record {
+            node.insert(name, value.clone())
+                .map_err(|e| DeserializeError {
+                    field: format!("{path}.{}", e.field),
+                    ..e
+                })?;
+        }
+
+        seed.deserialize(NodeDeserializer { node: &node, path })
+            .map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::widgets::{field_is, Button, Input, List, NumInput, Radio, Section};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Config {
+        name: String,
+        server: Server,
+        tags: Vec<String>,
+        proxy: String,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    fn form() -> Form {
+        let mut form = Form::new();
+        form.push(Section::new("general", "General"));
+        form.push(Input::new("name", "Name", "tuwi"));
+        form.push(Input::new("server.host", "Host", "localhost"));
+        form.push(NumInput::new("server.port", "Port", 8080, (1, 65535), 1));
+        form.push(List::new("tags", "Tags", vec!["a".to_string()]));
+        form.push(Radio::new(
+            "proxy",
+            "Proxy",
+            vec![("No", "n"), ("Yes", "y")],
+            "n",
+        ));
+        form.push(Input::new("proxy_url", "Proxy URL", ""));
+        form.visible_when("proxy_url", field_is("proxy", "y"));
+        form.push(Button::new("save", "Save", |_| {}));
+        form
+    }
+
+    /// The section header, the button and the hidden proxy URL would all be
+    /// unknown fields if they were passed in.
+    #[test]
+    fn nested_names_fill_nested_structs() {
+        assert_eq!(
+            form().deserialize::<Config>().unwrap(),
+            Config {
+                name: "tuwi".to_string(),
+                server: Server {
+                    host: "localhost".to_string(),
+                    port: 8080,
+                },
+                tags: vec!["a".to_string()],
+                proxy: "n".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn errors_name_the_field() {
+        let mut form = form();
+        form.remove("server.port");
+        form.push(Input::new("server.port", "Port", "eighty"));
+
+        let e = form.deserialize::<Config>().unwrap_err();
+        assert_eq!(e.field, "server.port");
+        assert_eq!(
+            e.to_string(),
+            "`server.port`: expected a positive integer, found `eighty`"
+        );
+    }
+
+    #[test]
+    fn a_value_cant_also_hold_fields() {
+        let mut form = form();
+        form.push(Input::new("server", "Server", "x"));
+
+        let e = form.deserialize::<Config>().unwrap_err();
+        assert_eq!(e.field, "server");
+    }
+}
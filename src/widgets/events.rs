@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    sync::{mpsc, Arc},
+};
+
+use super::{Form, FormValue};
+
+/// Something that happened to a [`Form`] while handling input, or a change
+/// made with [`Form::fill`], [`Form::reset`] or [`Form::remove`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormEvent {
+    ValueChanged {
+        name: String,
+        old: FormValue,
+        new: FormValue,
+    },
+    FocusChanged {
+        from: String,
+        to: String,
+    },
+    /// The form was submitted, by the button named `name` or (when empty)
+    /// by Ctrl+S on a form without one.
+    Submitted {
+        name: String,
+    },
+    /// An action button named `name` was pressed and its handler ran.
+    Action {
+        name: String,
+    },
+    Cancelled,
+}
+
+/// What handling one input event did to a [`Form`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FormOutcome {
+    #[default]
+    Continue,
+    /// Every item's value, as from [`Form::values`].
+    Submitted(HashMap<String, FormValue>),
+    Cancelled,
+    /// The action button with this name was pressed.
+    Action(String),
+    /// A button's handler failed with this message, which is also shown in
+    /// the form.
+    Error(String),
+}
+
+impl FormOutcome {
+    /// Whether the form is finished with, i.e. submitted or cancelled.
+    pub fn is_done(&self) -> bool {
+        matches!(self, FormOutcome::Submitted(_) | FormOutcome::Cancelled)
+    }
+}
+
+impl FormEvent {
+    /// Whether the event is about the item named `name`.
+    pub fn concerns(&self, name: &str) -> bool {
+        match self {
+            FormEvent::ValueChanged { name: n, .. }
+            | FormEvent::Submitted { name: n }
+            | FormEvent::Action { name: n } => n == name,
+            FormEvent::FocusChanged { from, to } => from == name || to == name,
+            FormEvent::Cancelled => false,
+        }
+    }
+}
+
+pub type FormCallback = Arc<dyn Fn(&mut Form, &FormEvent) + Send + Sync>;
+
+impl Form {
+    /// Returns a receiver for every event from now on.
+    pub fn subscribe(&mut self) -> mpsc::Receiver<FormEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.listeners.push(tx);
+        rx
+    }
+
+    /// Calls `f` with each event about the item named `name`. The form is
+    /// passed back in, so dependent items can be updated.
+    pub fn on(&mut self, name: &str, f: impl Fn(&mut Form, &FormEvent) + Send + Sync + 'static) {
+        self.callbacks
+            .entry(name.to_string())
+            .or_default()
+            .push(Arc::new(f));
+    }
+
+    /// Reports that the user backed out of the form.
+    pub fn cancel(&mut self) {
+        self.events.push(FormEvent::Cancelled);
+        self.flush_events();
+    }
+
+    pub(super) fn snapshot(&self) -> Vec<(String, FormValue)> {
+        self.items
+            .iter()
+            .map(|i| {
+                let i = i.read().unwrap();
+                (i.name(), i.value())
+            })
+            .collect()
+    }
+
+    /// Queues changes since `before` (and whatever `outcome` reports), then
+    /// sends out everything queued.
+    pub(super) fn finish(
+        &mut self,
+        before: Vec<(String, FormValue)>,
+        outcome: FormOutcome,
+    ) -> FormOutcome {
+        if before.len() == self.items.len() {
+            for ((name, old), (_, new)) in before.into_iter().zip(self.snapshot()) {
+                if old != new {
+                    self.events.push(FormEvent::ValueChanged { name, old, new });
+                }
+            }
+        }
+
+        // Enter and clicks press the focused button; Ctrl+S the first one
+        // that's shown and enabled.
+        let submitter = || {
+            let focused = self.items[self.focused].read().unwrap();
+            match focused.is_submit() {
+                true => focused.name(),
+                false => self
+                    .first_submit()
+                    .map(|i| self.items[i].read().unwrap().name())
+                    .unwrap_or_default(),
+            }
+        };
+
+        match &outcome {
+            FormOutcome::Submitted(_) => {
+                let name = submitter();
+                self.events.push(FormEvent::Submitted { name });
+            }
+            FormOutcome::Action(name) => {
+                let name = name.clone();
+                self.events.push(FormEvent::Action { name });
+            }
+            FormOutcome::Cancelled => self.events.push(FormEvent::Cancelled),
+            _ => {}
+        }
+
+        self.flush_events();
+        outcome
+    }
+
+    pub(super) fn flush_events(&mut self) {
+        for event in std::mem::take(&mut self.events) {
+            self.listeners.retain(|tx| tx.send(event.clone()).is_ok());
+
+            let callbacks = self
+                .callbacks
+                .iter()
+                .filter(|(name, _)| event.concerns(name))
+                .flat_map(|(_, c)| c.clone())
+                .collect::<Vec<_>>();
+
+            for callback in callbacks {
+                callback(self, &event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+    use crate::widgets::{field_is, Button, Input};
+
+    #[test]
+    fn fill_and_reset_report_changes() {
+        let mut form = Form::new();
+        form.push(Input::new("host", "Host", "localhost"));
+        form.push(Input::new("port", "Port", "80"));
+
+        let events = form.subscribe();
+        form.fill([("host", "example.com")]);
+        form.reset();
+        let changed = |old: &str, new: &str| FormEvent::ValueChanged {
+            name: "host".to_string(),
+            old: FormValue::Text(old.to_string()),
+            new: FormValue::Text(new.to_string()),
+        };
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            vec![
+                changed("localhost", "example.com"),
+                changed("example.com", "localhost")
+            ]
+        );
+    }
+
+    #[test]
+    fn ctrl_s_names_the_button_it_pressed() {
+        let mut form = Form::new();
+        form.push(Input::new("mode", "Mode", "simple"));
+        form.push(Button::new("advanced", "Save advanced", |_| {}));
+        form.push(Button::new("save", "Save", |_| {}));
+        form.visible_when("advanced", field_is("mode", "advanced"));
+        form.focus_by_name("mode");
+
+        let events = form.subscribe();
+        let outcome = form.handle_input(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert!(matches!(outcome, FormOutcome::Submitted(_)));
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            vec![FormEvent::Submitted {
+                name: "save".to_string()
+            }]
+        );
+    }
+}
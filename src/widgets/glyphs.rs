@@ -0,0 +1,296 @@
+use std::{
+    env,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        OnceLock,
+    },
+};
+
+use ratatui::{symbols::border, widgets::BorderType};
+
+/// The characters widgets draw state with. See [`glyphs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    /// Stands in for every border type but thick and double, when set.
+    pub border: Option<border::Set>,
+    /// Stands in for thick and double borders, when set.
+    pub heavy_border: Option<border::Set>,
+    /// Slider bar, when the theme is monochrome.
+    pub slider_fill: &'static str,
+    pub slider_track: &'static str,
+    /// Before open sections and group entries.
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    /// Between the values in a group entry's summary.
+    pub separator: &'static str,
+    /// Between the steps in a wizard's header.
+    pub breadcrumb: &'static str,
+    /// Frames of the spinner shown while an item is being checked.
+    pub spinner: &'static [&'static str],
+}
+
+impl Glyphs {
+    pub const UNICODE: Glyphs = Glyphs {
+        border: None,
+        heavy_border: None,
+        slider_fill: "█",
+        slider_track: "░",
+        expanded: "▾ ",
+        collapsed: "▸ ",
+        separator: " · ",
+        breadcrumb: " › ",
+        spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+    };
+
+    /// 7-bit ASCII only, for serial consoles and legacy terminals.
+    pub const ASCII: Glyphs = Glyphs {
+        border: Some(border::Set {
+            top_left: "+",
+            top_right: "+",
+            bottom_left: "+",
+            bottom_right: "+",
+            vertical_left: "|",
+            vertical_right: "|",
+            horizontal_top: "-",
+            horizontal_bottom: "-",
+        }),
+        heavy_border: Some(border::Set {
+            top_left: "#",
+            top_right: "#",
+            bottom_left: "#",
+            bottom_right: "#",
+            vertical_left: "#",
+            vertical_right: "#",
+            horizontal_top: "=",
+            horizontal_bottom: "=",
+        }),
+        slider_fill: "#",
+        slider_track: ".",
+        expanded: "v ",
+        collapsed: "> ",
+        separator: ", ",
+        breadcrumb: " > ",
+        spinner: &["|", "/", "-", "\\"],
+    };
+
+    /// The characters to draw `border_type` with.
+    pub fn border_set(&self, border_type: BorderType) -> border::Set {
+        let replacement = match border_type {
+            BorderType::Thick | BorderType::Double => self.heavy_border,
+            _ => self.border,
+        };
+
+        replacement.unwrap_or(border_type.to_border_set())
+    }
+}
+
+const DETECT: u8 = 0;
+const UNICODE: u8 = 1;
+const ASCII: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(DETECT);
+static DETECTED: OnceLock<bool> = OnceLock::new();
+
+/// Forces ASCII-only glyphs on or off for every widget. `None` goes back to
+/// [`detect_ascii`].
+pub fn set_ascii(ascii: Option<bool>) {
+    let mode = match ascii {
+        None => DETECT,
+        Some(false) => UNICODE,
+        Some(true) => ASCII,
+    };
+
+    MODE.store(mode, Ordering::Relaxed);
+}
+
+pub fn is_ascii() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        UNICODE => false,
+        ASCII => true,
+        _ => *DETECTED.get_or_init(detect_ascii),
+    }
+}
+
+/// [`Glyphs::ASCII`] if set with [`set_ascii`] or detected, otherwise
+/// [`Glyphs::UNICODE`].
+pub fn glyphs() -> &'static Glyphs {
+    match is_ascii() {
+        true => &Glyphs::ASCII,
+        false => &Glyphs::UNICODE,
+    }
+}
+
+/// Guesses whether the terminal can only show ASCII: a `TERM` known not to
+/// support more (including the Linux console, whose fonts lack most of the
+/// glyphs), a locale that isn't UTF-8, or on Windows the old console host
+/// (outside Windows Terminal).
+pub fn detect_ascii() -> bool {
+    detect_ascii_from(|name| env::var(name).ok())
+}
+
+/// [`detect_ascii`], reading environment variables with `lookup`.
+pub fn detect_ascii_from(lookup: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(term) = lookup("TERM") {
+        let base = term.split('-').next().unwrap_or_default();
+        if matches!(
+            base,
+            "dumb" | "ansi" | "cons25" | "linux" | "vt52" | "vt100" | "vt102" | "vt220" | "vt320"
+        ) {
+            return true;
+        }
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|v| lookup(v).filter(|l| !l.is_empty()));
+
+    if let Some(locale) = locale {
+        let locale = locale.to_ascii_lowercase();
+        return !(locale.contains("utf-8") || locale.contains("utf8"));
+    }
+
+    cfg!(windows) && lookup("WT_SESSION").is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crossterm::event::KeyCode;
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    use super::*;
+    use crate::widgets::{
+        tests::screen, Checkbox, Form, FormItem, FormValue, Group, Input, Section, Slider, Theme,
+    };
+
+    /// A section, checkbox and slider, a group with one entry expanded and
+    /// one not, and a collapsed section, without colour.
+    fn snapshot(glyphs: Glyphs) -> Vec<String> {
+        let mut form = Form::new();
+        form.push(Section::new("net", "Network"));
+        form.push(Checkbox::new("dhcp", "DHCP", true));
+        form.push(Slider::new("mtu", "MTU", (0, 100), 50, None));
+        form.push(Group::new("hosts", "Hosts", || {
+            let mut entry = Form::new();
+            entry.push(Input::new("host", "Host", ""));
+            entry.push(Input::new("port", "Port", ""));
+            entry
+        }));
+        form.push(Section::new("extra", "Extra").collapsed());
+        form.set_theme(Theme {
+            glyphs: Some(glyphs),
+            ..Theme::monochrome()
+        });
+
+        let record = |host: &str, port: &str| {
+            [("host", host), ("port", port)]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), FormValue::Text(v.to_string())))
+                .collect()
+        };
+        {
+            let mut hosts = form.get_mut::<Group>("hosts").unwrap();
+            hosts.set_value(FormValue::Records(vec![
+                record("db1", "5432"),
+                record("db2", "5433"),
+            ]));
+            hosts.input(KeyCode::Enter.into());
+        }
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 23));
+        form.ren(buf.area, &mut buf);
+        screen(&buf)
+            .into_iter()
+            .map(|row| row.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn draws_unicode_glyphs() {
+        assert_eq!(
+            snapshot(Glyphs::UNICODE),
+            [
+                "▾ Network",
+                "",
+                "┌DHCP──────────────────────┐",
+                "│[x]                       │",
+                "└──────────────────────────┘",
+                "",
+                "┌MTU: 50───────────────────┐",
+                "│█████████████░░░░░░░░░░░░░│",
+                "└──────────────────────────┘",
+                "",
+                "┌Hosts─────────────────────┐",
+                "│▾ 1. db1 · 5432           │",
+                "│  ┏> Host━━━━━━━━━━━━━━┓  │",
+                "│  ┃db1                 ┃  │",
+                "│  ┗━━━━━━━━━━━━━━━━━━━━┛  │",
+                "│                          │",
+                "│  ┌Port────────────────┐  │",
+                "│  │5432                │  │",
+                "│  └────────────────────┘  │",
+                "│▸ 2. db2 · 5433           │",
+                "│+ Add                     │",
+                "└──────────────────────────┘",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn draws_ascii_glyphs() {
+        assert_eq!(
+            snapshot(Glyphs::ASCII),
+            [
+                "v Network",
+                "",
+                "+DHCP----------------------+",
+                "|[x]                       |",
+                "+--------------------------+",
+                "",
+                "+MTU: 50-------------------+",
+                "|#############.............|",
+                "+--------------------------+",
+                "",
+                "+Hosts---------------------+",
+                "|v 1. db1, 5432            |",
+                "|  #> Host==============#  |",
+                "|  #db1                 #  |",
+                "|  #====================#  |",
+                "|                          |",
+                "|  +Port----------------+  |",
+                "|  |5432                |  |",
+                "|  +--------------------+  |",
+                "|> 2. db2, 5433            |",
+                "|+ Add                     |",
+                "+--------------------------+",
+                "",
+            ]
+        );
+    }
+
+    /// Runs `detect_ascii_from` as if only `vars` were set.
+    fn detect_with(vars: &[(&str, &str)]) -> bool {
+        let vars = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>();
+        detect_ascii_from(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn detects_ascii_terminals_and_locales() {
+        assert!(detect_with(&[("TERM", "linux"), ("LANG", "en_US.UTF-8")]));
+        assert!(detect_with(&[("TERM", "vt220")]));
+        assert!(detect_with(&[("TERM", "xterm-256color"), ("LANG", "C")]));
+        assert!(!detect_with(&[
+            ("TERM", "xterm-256color"),
+            ("LANG", "en_US.UTF-8")
+        ]));
+        // LC_ALL overrides LANG, and empty values are skipped.
+        assert!(!detect_with(&[("LC_ALL", "de_DE.utf8"), ("LANG", "C")]));
+        assert!(detect_with(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")]));
+        assert!(!detect_with(&[("LC_ALL", ""), ("LANG", "en_US.UTF-8")]));
+    }
+}
@@ -0,0 +1,492 @@
+use std::sync::Arc;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use super::{
+    has_command_modifier, render_compact_label, shown_title, Form, FormItem, FormValue, Theme,
+};
+
+pub type GroupTemplate = Arc<dyn Fn() -> Form + Send + Sync>;
+
+/// A list of records, each edited with its own copy of a template [`Form`].
+///
+/// Entries are shown one per line. Enter (or a click on the selected entry)
+/// expands one for editing and Esc folds it back; Delete removes it and
+/// Alt/Ctrl+Up/Down reorder it. The last row adds a new entry. The form the
+/// group is in won't submit while an entry fails its own validation.
+#[derive(Clone)]
+pub struct Group {
+    pub name: String,
+    pub title: String,
+    pub entries: Vec<Form>,
+    pub focused: bool,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
+    template: GroupTemplate,
+    /// Selected row; `entries.len()` is the "add" row.
+    cursor: usize,
+    editing: Option<usize>,
+}
+
+impl Group {
+    /// `template` builds the form for one entry.
+    pub fn new(
+        name: &str,
+        title: &str,
+        template: impl Fn() -> Form + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            entries: vec![],
+            focused: false,
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
+            template: Arc::new(template),
+            cursor: 0,
+            editing: None,
+        }
+    }
+
+    /// Drops the border, with the title and entry count on the first row.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
+        }
+    }
+
+    /// A fresh entry from the template, in the group's theme.
+    fn entry(&self) -> Form {
+        let mut entry = (self.template)();
+        entry.set_theme(self.theme);
+        entry
+    }
+
+    /// Appends an entry from the template and starts editing it.
+    pub fn add(&mut self) {
+        self.entries.push(self.entry());
+        self.cursor = self.entries.len() - 1;
+        self.editing = Some(self.cursor);
+    }
+
+    pub fn remove(&mut self, i: usize) {
+        if i >= self.entries.len() {
+            return;
+        }
+
+        self.entries.remove(i);
+        self.editing = None;
+        self.cursor = self.cursor.min(self.entries.len());
+    }
+
+    /// Swaps entry `i` with the one `by` rows away, keeping it selected.
+    fn shift(&mut self, i: usize, by: isize) {
+        let j = i as isize + by;
+        if i >= self.entries.len() || j < 0 || j as usize >= self.entries.len() {
+            return;
+        }
+
+        self.entries.swap(i, j as usize);
+        self.cursor = j as usize;
+    }
+
+    /// Each entry's own validation failures, as `Entry n, name: message`.
+    /// The group's form counts these as errors on the group.
+    pub fn validate(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .enumerate()
+            .flat_map(|(i, entry)| {
+                entry
+                    .validate()
+                    .into_iter()
+                    .map(move |(name, message)| format!("Entry {}, {name}: {message}", i + 1))
+            })
+            .collect()
+    }
+
+    /// The entry being edited, if it's still there. `entries` can change
+    /// under the group, so this is checked before every use.
+    fn expanded(&self) -> Option<usize> {
+        self.editing.filter(|i| *i < self.entries.len())
+    }
+
+    /// Brings the selection back in range after `entries` changed.
+    fn settle(&mut self) {
+        self.editing = self.expanded();
+        self.cursor = self.cursor.min(self.entries.len());
+    }
+
+    /// Rows the expanded entry takes, if any.
+    fn editor_height(&self) -> u16 {
+        self.expanded()
+            .map(|i| self.entries[i].content_height())
+            .unwrap_or_default()
+    }
+
+    /// The entry's values on one line, in item order.
+    fn summary(&self, entry: &Form) -> String {
+        entry
+            .slurp()
+            .into_iter()
+            .map(|(_, v)| v)
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join(self.theme.glyphs().separator)
+    }
+
+    /// The screen row of each entry's line (and the "add" line last), and
+    /// the area of the expanded entry.
+    fn rows(&self, area: Rect) -> (Vec<u16>, Option<Rect>) {
+        let x = match self.compact {
+            true => area.x + 2,
+            false => area.x + 1,
+        };
+
+        let mut y = area.y + 1;
+        let mut rows = Vec::new();
+        let mut editor = None;
+        for i in 0..=self.entries.len() {
+            rows.push(y);
+            y += 1;
+            if self.expanded() == Some(i) {
+                let height = self.editor_height();
+                editor = Some(Rect {
+                    x: x + 2,
+                    y,
+                    width: area.right().saturating_sub(x + 3),
+                    height,
+                });
+                y += height;
+            }
+        }
+
+        (rows, editor)
+    }
+}
+
+impl FormItem for Group {
+    fn ren(&self, a: Rect, b: &mut Buffer) {
+        self.clone().render(a, b);
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+        for entry in &mut self.entries {
+            entry.set_theme(*theme);
+        }
+    }
+
+    fn value(&self) -> FormValue {
+        FormValue::Records(self.entries.iter().map(|e| e.values()).collect())
+    }
+
+    /// Replaces every entry, filling a fresh template per record.
+    fn set_value(&mut self, v: FormValue) -> bool {
+        let records = match v {
+            FormValue::Records(r) => r,
+            FormValue::Null => vec![],
+            _ => return false,
+        };
+
+        self.entries = records
+            .into_iter()
+            .map(|r| {
+                let mut entry = self.entry();
+                entry.fill(r);
+                entry
+            })
+            .collect();
+
+        self.cursor = 0;
+        self.editing = None;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.set_value(FormValue::Null);
+    }
+
+    fn should_prevent_q(&self) -> bool {
+        self.expanded().is_some_and(|i| self.entries[i].prevent_q)
+    }
+
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
+        if self.expanded().is_some() {
+            return true;
+        }
+
+        match k.code {
+            KeyCode::Up | KeyCode::Down if has_command_modifier(&k) => true,
+            KeyCode::Up => self.cursor.min(self.entries.len()) > 0,
+            KeyCode::Down => self.cursor < self.entries.len(),
+            _ => false,
+        }
+    }
+
+    fn height(&self) -> u16 {
+        let chrome = match self.compact {
+            true => 2,  // Title + Add
+            false => 3, // Border + Add
+        };
+
+        self.entries.len() as u16 + chrome + self.editor_height()
+    }
+
+    fn input(&mut self, k: KeyEvent) {
+        self.settle();
+        if let Some(i) = self.editing {
+            match k.code {
+                KeyCode::Esc => self.editing = None,
+                _ => {
+                    self.entries[i].handle_input(k);
+                }
+            }
+
+            return;
+        }
+
+        let entry = self.cursor < self.entries.len();
+        match k.code {
+            KeyCode::Up if has_command_modifier(&k) => self.shift(self.cursor, -1),
+            KeyCode::Down if has_command_modifier(&k) => self.shift(self.cursor, 1),
+            KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Down => self.cursor = (self.cursor + 1).min(self.entries.len()),
+            KeyCode::Enter | KeyCode::Char(' ') if entry => self.editing = Some(self.cursor),
+            KeyCode::Enter | KeyCode::Char(' ') => self.add(),
+            KeyCode::Delete | KeyCode::Backspace if entry => self.remove(self.cursor),
+            _ => {}
+        }
+    }
+
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {
+        self.settle();
+        let (rows, editor) = self.rows(a);
+        if let (Some(i), Some(editor)) = (self.editing, editor) {
+            if editor.contains((m.column, m.row).into())
+                || m.kind != MouseEventKind::Down(MouseButton::Left)
+            {
+                self.entries[i].handle_mouse(m);
+                return;
+            }
+        }
+
+        if m.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+
+        let Some(row) = rows.iter().position(|y| *y == m.row) else {
+            return;
+        };
+
+        match row {
+            _ if row == self.entries.len() => self.add(),
+            _ if self.editing == Some(row) => self.editing = None,
+            _ if self.cursor == row => self.editing = Some(row),
+            _ => {
+                self.cursor = row;
+                self.editing = None;
+            }
+        }
+    }
+}
+
+impl Widget for Group {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let label = shown_title(self.show_title, &self.title);
+
+        if self.compact {
+            let header = render_compact_label(area, buf, label, self.focused, &self.theme);
+            buf.set_stringn(
+                header.x,
+                header.y,
+                format!("{} entries", self.entries.len()),
+                header.width as usize,
+                self.theme.muted,
+            );
+        } else {
+            self.theme
+                .block(label.to_string(), self.focused)
+                .render(area, buf);
+        }
+
+        // No bottom border to leave room for when compact.
+        let bottom = match self.compact {
+            true => area.bottom(),
+            false => area.bottom().saturating_sub(1),
+        };
+
+        let x = match self.compact {
+            true => area.x + 2,
+            false => area.x + 1,
+        };
+
+        let (rows, editor) = self.rows(area);
+        for (i, y) in rows.iter().enumerate() {
+            let row = Rect {
+                x,
+                y: *y,
+                width: area.right().saturating_sub(x + 1),
+                height: 1,
+            };
+
+            if row.bottom() > bottom {
+                break;
+            }
+
+            let selected = self.focused && self.cursor == i;
+            let style = match selected {
+                true => self.theme.selection,
+                false => Style::new(),
+            };
+
+            let line = match self.entries.get(i) {
+                Some(entry) => Line::from(vec![
+                    Span::styled(
+                        match self.expanded() == Some(i) {
+                            true => self.theme.glyphs().expanded,
+                            false => self.theme.glyphs().collapsed,
+                        },
+                        style,
+                    ),
+                    Span::styled(format!("{}. {}", i + 1, self.summary(entry)), style),
+                ]),
+                None => Line::styled("+ Add", style.italic()),
+            };
+
+            line.render(row, buf);
+        }
+
+        if let (Some(i), Some(editor)) = (self.expanded(), editor) {
+            self.entries[i].ren(editor.intersection(area), buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::widgets::{tests::screen, FormOutcome, Input, Required};
+
+    fn server() -> Form {
+        let mut form = Form::new();
+        form.push(Input::new("host", "Host", ""));
+        form.push(Input::new("port", "Port", ""));
+        form.validator("host", Required);
+        form
+    }
+
+    fn servers() -> Form {
+        let mut form = Form::new();
+        form.push(Input::new("name", "Name", "prod"));
+        form.push(Group::new("servers", "Servers", server));
+        form.get_mut::<Group>("servers").unwrap().add();
+        form.focus_by_name("servers");
+        form
+    }
+
+    #[test]
+    fn clicks_reach_fields_in_an_expanded_entry() {
+        // Off the screen's origin and scrolled, so the entry is drawn at a
+        // different position in the form than on screen.
+        let mut form = servers();
+        let area = Rect::new(3, 2, 40, 12);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 50, 20));
+        form.ren(area, &mut buf);
+        for _ in 0..3 {
+            form.handle_mouse(MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                column: 5,
+                row: 5,
+                modifiers: KeyModifiers::NONE,
+            });
+        }
+
+        buf.reset();
+        form.ren(area, &mut buf);
+        let rows = screen(&buf);
+        let row = rows.iter().position(|r| r.contains("Port")).unwrap() as u16;
+        let column = rows[row as usize].find("Port").unwrap() as u16;
+        form.handle_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row: row + 1,
+            modifiers: KeyModifiers::NONE,
+        });
+        form.handle_input(KeyCode::Char('8'));
+
+        let FormValue::Records(records) = &form.values()["servers"] else {
+            panic!("servers isn't a list of records");
+        };
+        assert_eq!(records[0]["port"], FormValue::Text("8".to_string()));
+        assert_eq!(records[0]["host"], FormValue::Text(String::new()));
+    }
+
+    #[test]
+    fn invalid_entries_block_the_form() {
+        let mut form = servers();
+        assert_eq!(
+            form.validate(),
+            vec![("servers".to_string(), "Entry 1, host: Required".to_string())]
+        );
+
+        let outcome = form.handle_input(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+        assert!(matches!(outcome, FormOutcome::Continue));
+
+        form.get_mut::<Group>("servers").unwrap().entries[0].fill([("host", "db1")]);
+        assert!(form.is_valid());
+    }
+
+    #[test]
+    fn entries_can_change_while_one_is_expanded() {
+        let mut form = servers();
+        form.get_mut::<Group>("servers").unwrap().add();
+        form.get_mut::<Group>("servers").unwrap().entries.clear();
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 40, 20));
+        form.ren(buf.area, &mut buf);
+        assert!(screen(&buf).iter().any(|r| r.contains("+ Add")));
+
+        // The stale selection is dropped, so Enter adds rather than expands.
+        form.handle_input(KeyCode::Enter);
+        let group = form.get::<Group>("servers").unwrap();
+        assert_eq!(group.entries.len(), 1);
+        assert_eq!(group.expanded(), Some(0));
+    }
+}
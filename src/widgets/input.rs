@@ -1,35 +1,25 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget},
 };
 
-use super::FormItem;
-
-pub fn add_cursor<'a>(s: String, c: usize) -> Line<'a> {
-    Line::from(vec![
-        Span::raw(s[..c].to_string()),
-        Span::styled(
-            s.clone().chars().nth(c).unwrap_or(' ').to_string(),
-            Style::new().bg(Color::Yellow),
-        ),
-        Span::raw(match c == s.len() {
-            true => String::new(),
-            false => s[c + 1..].to_string(),
-        }),
-    ])
-}
+use super::{
+    is_horizontal_arrow, render_compact_label, shown_title, value_area, FormItem, FormValue,
+    TextBuffer, Theme,
+};
 
 #[derive(Clone)]
 pub struct Input {
     pub name: String,
     pub title: String,
-    pub value: String,
+    pub value: TextBuffer,
     pub focused: bool,
-    cursor: usize,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
+    initial: String,
 }
 
 impl Input {
@@ -37,9 +27,20 @@ impl Input {
         Self {
             name: name.to_string(),
             title: title.to_string(),
-            value: value.to_string(),
+            value: TextBuffer::new(value),
             focused: false,
-            cursor: value.len(),
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
+            initial: value.to_string(),
+        }
+    }
+
+    /// Draws on one row as `Title: value`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
         }
     }
 }
@@ -61,46 +62,67 @@ impl FormItem for Input {
         true
     }
 
-    fn value(&self) -> Option<String> {
-        Some(self.value.clone())
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
+
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
+        is_horizontal_arrow(&k)
+    }
+
+    fn value(&self) -> FormValue {
+        FormValue::Text(self.value.to_string())
+    }
+
+    fn set_value(&mut self, v: FormValue) -> bool {
+        if matches!(v, FormValue::List(_)) {
+            return false;
+        }
+
+        self.value.set(&v.to_string());
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value.set(&self.initial);
     }
 
     fn name(&self) -> String {
         self.name.clone()
     }
 
-    fn input(&mut self, k: KeyCode) {
-        match k {
-            KeyCode::Backspace => {
-                if self.cursor > 0 {
-                    self.value.remove(self.cursor - 1);
-                    self.cursor -= 1;
-                }
-            }
-            KeyCode::Left => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.cursor < self.value.len() {
-                    self.cursor += 1;
-                }
-            }
-            KeyCode::Home => {
-                self.cursor = 0;
-            }
-            KeyCode::End => {
-                self.cursor = self.value.len();
-            }
-            KeyCode::Delete => {
-                self.value.remove(self.cursor);
-            }
-            KeyCode::Char(c) => {
-                self.value.insert(self.cursor, c);
-                self.cursor += 1;
-            }
-            _ => {}
+    fn input(&mut self, k: KeyEvent) {
+        self.value.input(k);
+    }
+
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {
+        if m.kind == MouseEventKind::Down(MouseButton::Left) {
+            let label = shown_title(self.show_title, &self.title);
+
+            let area = value_area(a, label, self.compact);
+            self.value.place_cursor(
+                m.column.saturating_sub(area.x) as usize,
+                area.width as usize,
+            );
         }
     }
 }
@@ -113,30 +135,27 @@ where
     where
         Self: Sized,
     {
-        let mut offset = 0;
-        let slice = match area.columns().count() < self.value.len() + 3
-            && self.cursor > area.columns().count()
-        {
-            false => self.value.clone(),
-            true => {
-                // slice with the cursor at the end
-                offset = self.cursor - area.columns().count() + 3;
-                self.value[offset..].to_string()
-            }
-        };
-
-        let block = Block::default()
-            .title(self.title)
-            .borders(Borders::ALL)
-            .border_style(match self.focused {
-                true => ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
-                false => ratatui::style::Style::default(),
-            });
-
-        Paragraph::new(match self.focused {
-            true => add_cursor(slice, self.cursor - offset),
-            false => Line::raw(self.value.clone()),
-        })
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
+
+            let area = render_compact_label(area, buf, label, self.focused, &self.theme);
+            Paragraph::new(self.value.line(
+                area.width as usize,
+                self.focused.then_some(self.theme.cursor),
+            ))
+            .render(area, buf);
+            return;
+        }
+
+        let block = self.theme.block(
+            shown_title(self.show_title, &self.title).to_string(),
+            self.focused,
+        );
+
+        Paragraph::new(self.value.line(
+            area.width.saturating_sub(2) as usize,
+            self.focused.then_some(self.theme.cursor),
+        ))
         .block(block)
         .render(area, buf);
     }
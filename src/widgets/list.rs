@@ -1,18 +1,21 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
-    style::Style,
-    text::Line,
-    widgets::{Block, Borders, Paragraph, Widget},
+    style::Stylize,
+    widgets::{Paragraph, Widget},
 };
+use unicode_width::UnicodeWidthStr;
 
-use super::{add_cursor, FormItem};
+use super::{
+    compact_split, is_horizontal_arrow, render_compact_label, shown_title, FormItem, FormValue,
+    TextBuffer, Theme,
+};
 
 #[derive(Clone)]
 pub enum ListCursor {
     Item(usize),
-    Input(usize),
+    Input,
 }
 
 #[derive(Clone)]
@@ -21,24 +24,73 @@ pub struct List {
     pub title: String,
     pub values: Vec<String>,
     pub focused: bool,
-    buffer: String,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
+    buffer: TextBuffer,
     cursor: ListCursor,
+    initial: Vec<String>,
 }
 
 impl List {
     pub fn new(name: &str, title: &str, values: Vec<String>) -> Self {
         Self {
+            initial: values.clone(),
             values,
             name: name.to_string(),
             title: title.to_string(),
             focused: false,
-            cursor: ListCursor::Input(0),
-            buffer: String::new(),
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
+            cursor: ListCursor::Input,
+            buffer: TextBuffer::default(),
+        }
+    }
+
+    /// Draws on one row as `Title: value value input`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
         }
     }
 
-    fn set_editing_cursor_position(&mut self, i: usize) {
-        self.cursor = ListCursor::Input(i);
+    /// The value, bracketed when selected if the theme is monochrome.
+    fn value_text(&self, value: &str, selected: bool) -> String {
+        match self.theme.monochrome && selected {
+            true => format!("[{value}]"),
+            false => value.to_string(),
+        }
+    }
+
+    /// Where each value and the input go when compact.
+    fn compact_areas(&self, area: Rect) -> (Vec<Rect>, Rect) {
+        let label = shown_title(self.show_title, &self.title);
+
+        let area = compact_split(area, label).1;
+        let mut x = area.x;
+        let values = self
+            .values
+            .iter()
+            .map(|v| {
+                let r = Rect {
+                    x,
+                    width: (v.width() as u16 + 2).min(area.right() - x),
+                    ..area
+                };
+                x = (r.right() + 1).min(area.right());
+                r
+            })
+            .collect();
+
+        let input = Rect {
+            x,
+            width: area.right() - x,
+            ..area
+        };
+
+        (values, input)
     }
 }
 
@@ -59,89 +111,139 @@ impl FormItem for List {
         self.focused = false;
     }
 
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn value(&self) -> FormValue {
+        FormValue::List(self.values.clone())
+    }
+
+    fn set_value(&mut self, v: FormValue) -> bool {
+        self.values = match v {
+            FormValue::List(l) => l,
+            FormValue::Null => vec![],
+            _ => return false,
+        };
+
+        self.buffer.clear();
+        self.cursor = ListCursor::Input;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.values = self.initial.clone();
+        self.buffer.clear();
+        self.cursor = ListCursor::Input;
+    }
+
     fn should_prevent_q(&self) -> bool {
-        matches!(self.cursor, ListCursor::Input(_))
+        matches!(self.cursor, ListCursor::Input)
     }
 
-    fn should_prevent_nav(&self, k: KeyCode) -> bool {
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
         match self.cursor {
-            ListCursor::Item(i) => (i != 0 && KeyCode::Up == k) || KeyCode::Down == k,
-            _ => KeyCode::Up == k,
+            ListCursor::Item(i) => (i != 0 && KeyCode::Up == k.code) || KeyCode::Down == k.code,
+            _ => KeyCode::Up == k.code || is_horizontal_arrow(&k),
         }
     }
 
     fn height(&self) -> u16 {
-        self.values.len() as u16 + 4 // Border + Input + Spacer
+        match self.compact {
+            true => 1,
+            false => self.values.len() as u16 + 4, // Border + Input + Spacer
+        }
     }
 
-    fn input(&mut self, k: KeyCode) {
+    fn input(&mut self, k: KeyEvent) {
         match self.cursor {
-            ListCursor::Item(i) => match k {
-                KeyCode::Up => {
-                    if i > 0 {
-                        self.cursor = ListCursor::Item(i - 1);
-                    }
+            ListCursor::Item(i) => match k.code {
+                KeyCode::Up if i > 0 => {
+                    self.cursor = ListCursor::Item(i - 1);
                 }
                 KeyCode::Down => {
                     if i < self.values.len().saturating_sub(1) {
                         self.cursor = ListCursor::Item(i + 1);
                     } else {
-                        self.cursor = ListCursor::Input(self.buffer.len());
+                        self.cursor = ListCursor::Input;
                     }
                 }
-                KeyCode::Backspace | KeyCode::Delete => {
-                    if i < self.values.len() {
-                        self.values.remove(i);
-                        self.cursor = match self.values.len() {
-                            0 => ListCursor::Input(0),
-                            _ => ListCursor::Item(i.saturating_sub(1)),
-                        };
-                    }
+                KeyCode::Backspace | KeyCode::Delete if i < self.values.len() => {
+                    self.values.remove(i);
+                    self.cursor = match self.values.len() {
+                        0 => ListCursor::Input,
+                        _ => ListCursor::Item(i.saturating_sub(1)),
+                    };
                 }
                 _ => {}
             },
-            ListCursor::Input(i) => match k {
+            ListCursor::Input => match k.code {
                 KeyCode::Up => {
                     self.cursor = ListCursor::Item(self.values.len().saturating_sub(1));
                 }
-                KeyCode::Backspace => {
-                    if i > 0 {
-                        self.buffer.remove(i as usize - 1);
-                        self.set_editing_cursor_position(i - 1);
-                    }
-                }
-                KeyCode::Left => {
-                    if i > 0 {
-                        self.set_editing_cursor_position(i - 1);
-                    }
-                }
-                KeyCode::Right => {
-                    if i < self.buffer.len() {
-                        self.set_editing_cursor_position(i + 1);
-                    }
-                }
-                KeyCode::Home => {
-                    self.set_editing_cursor_position(0);
-                }
-                KeyCode::End => {
-                    self.set_editing_cursor_position(self.buffer.len());
-                }
-                KeyCode::Delete => {
-                    self.buffer.remove(i);
-                }
-                KeyCode::Char(c) => {
-                    self.buffer.insert(i, c);
-                    self.set_editing_cursor_position(i + 1);
-                }
                 KeyCode::Enter => {
-                    self.values.push(self.buffer.clone());
+                    self.values.push(self.buffer.to_string());
                     self.buffer.clear();
                     self.cursor = ListCursor::Item(self.values.len() - 1);
                 }
-                _ => {}
+                _ => {
+                    self.buffer.input(k);
+                }
             },
         }
     }
+
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {
+        if m.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+
+        if self.compact {
+            let (values, input) = self.compact_areas(a);
+            if let Some(i) = values
+                .iter()
+                .position(|r| r.contains((m.column, m.row).into()))
+            {
+                self.cursor = ListCursor::Item(i);
+            } else if input.contains((m.column, m.row).into()) {
+                self.cursor = ListCursor::Input;
+                self.buffer.place_cursor(
+                    m.column.saturating_sub(input.x) as usize,
+                    input.width as usize,
+                );
+            }
+
+            return;
+        }
+
+        // The borders aren't part of any row.
+        if m.row <= a.y || m.row + 1 >= a.bottom() {
+            return;
+        }
+
+        let row = (m.row - a.y - 1) as usize;
+        if row < self.values.len() {
+            self.cursor = ListCursor::Item(row);
+        } else if m.row == a.y + a.height.saturating_sub(2) {
+            self.cursor = ListCursor::Input;
+            self.buffer.place_cursor(
+                m.column.saturating_sub(a.x + 1) as usize,
+                a.width.saturating_sub(2) as usize,
+            );
+        }
+    }
 }
 
 impl Widget for List
@@ -152,29 +254,50 @@ where
     where
         Self: Sized,
     {
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
+
+            render_compact_label(area, buf, label, self.focused, &self.theme);
+            let (values, input) = self.compact_areas(area);
+            for (i, (value, r)) in self.values.iter().zip(values).enumerate() {
+                let is_selected = matches!(self.cursor, ListCursor::Item(j) if i == j);
+                Paragraph::new(self.value_text(value, is_selected))
+                    .style(match is_selected {
+                        true => self.theme.selection.reversed(),
+                        false => self.theme.item,
+                    })
+                    .alignment(Alignment::Center)
+                    .render(r, buf);
+            }
+
+            Paragraph::new(
+                self.buffer.line(
+                    input.width as usize,
+                    (self.focused && matches!(self.cursor, ListCursor::Input))
+                        .then_some(self.theme.cursor),
+                ),
+            )
+            .render(input, buf);
+            return;
+        }
+
         // Border
 
-        Block::default()
-            .title(self.title)
-            .borders(Borders::ALL)
-            .border_style(match self.focused {
-                true => ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
-                false => ratatui::style::Style::default(),
-            })
+        self.theme
+            .block(
+                shown_title(self.show_title, &self.title).to_string(),
+                self.focused,
+            )
             .render(area, buf);
 
         // Values
 
         for (i, value) in self.values.iter().enumerate() {
             let is_selected = matches!(self.cursor, ListCursor::Item(j) if i == j);
-            Paragraph::new(value.to_string())
+            Paragraph::new(self.value_text(value, is_selected))
                 .style(match is_selected {
-                    true => Style::default()
-                        .fg(ratatui::style::Color::White)
-                        .bg(ratatui::style::Color::Yellow),
-                    false => Style::default()
-                        .fg(ratatui::style::Color::Black)
-                        .bg(ratatui::style::Color::White),
+                    true => self.theme.selection.reversed(),
+                    false => self.theme.item,
                 })
                 .alignment(Alignment::Center)
                 .render(
@@ -190,7 +313,7 @@ where
 
         // Spacer
         Paragraph::new("---")
-            .style(Style::default().fg(ratatui::style::Color::Gray))
+            .style(self.theme.muted)
             .alignment(Alignment::Center)
             .render(
                 Rect {
@@ -211,29 +334,10 @@ where
             height: 1,
         };
 
-        let mut offset = 0;
-        let input_cursor = match self.cursor {
-            ListCursor::Item(_) => self.buffer.len(),
-            ListCursor::Input(i) => i,
-        };
-
-        let slice = match area.columns().count() < self.buffer.len() + 3
-            && input_cursor > area.columns().count()
-        {
-            false => self.buffer.clone(),
-            true => {
-                // slice with the cursor at the end
-                offset = input_cursor - area.columns().count() + 3;
-                self.buffer[offset..].to_string()
-            }
-        };
-
-        Paragraph::new(
-            match self.focused && matches!(self.cursor, ListCursor::Input(_)) {
-                true => add_cursor(slice, input_cursor - offset),
-                false => Line::raw(self.buffer.clone()),
-            },
-        )
+        Paragraph::new(self.buffer.line(
+            input_area.width as usize,
+            (self.focused && matches!(self.cursor, ListCursor::Input)).then_some(self.theme.cursor),
+        ))
         .render(input_area, buf);
     }
 }
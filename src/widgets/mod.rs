@@ -1,36 +1,89 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::{mpsc::Sender, Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    thread,
+    time::{Duration, Instant},
+};
 
-use crossterm::event::KeyCode;
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
     buffer::Buffer,
-    layout::{self, Constraint, Flex, Layout, Margin, Position, Rect},
+    layout::{self, Constraint, Layout, Margin, Position, Rect},
     text::{Line, Text},
-    widgets::{Block, Borders, Clear, Padding, Paragraph, Widget, Wrap},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Widget, Wrap},
 };
 
+mod arrange;
 mod button;
+mod checkbox;
+mod compact;
+mod computed;
+mod conditions;
+mod convert;
+#[cfg(feature = "serde")]
+mod de;
+mod events;
+mod glyphs;
+mod group;
 mod input;
 mod list;
 mod num_input;
 mod radio;
 mod scroll;
 mod secret;
+mod section;
 mod slider;
+mod text;
+mod theme;
+#[cfg(feature = "toml")]
+mod theme_toml;
+mod validation;
+mod value;
+mod wizard;
 
+pub use arrange::*;
 pub use button::*;
+pub use checkbox::*;
+pub use compact::*;
+pub use computed::*;
+pub use conditions::*;
+pub use convert::*;
+#[cfg(feature = "serde")]
+pub use de::*;
+pub use events::*;
+pub use glyphs::*;
+pub use group::*;
 pub use input::*;
 pub use list::*;
 pub use num_input::*;
 pub use radio::*;
 pub use scroll::*;
 pub use secret::*;
+pub use section::*;
 pub use slider::*;
+pub use text::*;
+pub use theme::*;
+#[cfg(feature = "toml")]
+pub use theme_toml::*;
+pub use validation::*;
+pub use value::*;
+pub use wizard::*;
 
 #[derive(Clone)]
 pub struct Popup<'a> {
     pub title: Line<'a>,
     pub content: Text<'a>,
     pub form: Option<Form>,
+    /// Shown (and given input) instead of `form` when set.
+    pub wizard: Option<Wizard>,
+    /// Passed on to `form` and `wizard` when set. Otherwise they keep their
+    /// own.
+    pub theme: Option<Theme>,
 }
 
 impl Widget for Popup<'_> {
@@ -39,9 +92,13 @@ impl Widget for Popup<'_> {
         Self: Sized,
     {
         Clear.render(area, buf);
+        let theme = self.theme.unwrap_or_default();
         let block = Block::new()
             .title(self.title)
+            .title_style(theme.title)
             .borders(Borders::ALL)
+            .border_set(theme.glyphs().border_set(theme.border_type))
+            .border_style(theme.unfocused)
             .padding(Padding::symmetric(1, 1));
 
         let para = Paragraph::new(self.content).wrap(Wrap { trim: true });
@@ -49,61 +106,176 @@ impl Widget for Popup<'_> {
         let height = para.line_count(area.width) as u16;
         para.block(block).render(area, buf);
 
-        if let Some(form) = &self.form {
-            let layout = Layout::new(
-                layout::Direction::Vertical,
-                vec![Constraint::Length(height + 2), Constraint::Fill(1)],
-            )
-            .split(area);
+        let (form, header) = match (&self.wizard, &self.form) {
+            (Some(wizard), _) => match wizard.form() {
+                Some(form) => (form, wizard::HEADER_HEIGHT),
+                None => return,
+            },
+            (None, Some(form)) => (form, 0),
+            (None, None) => return,
+        };
+
+        let layout = Layout::new(
+            layout::Direction::Vertical,
+            vec![Constraint::Length(height + 2), Constraint::Fill(1)],
+        )
+        .split(area);
 
-            let form_height = form
-                .items
-                .iter()
-                .fold(0, |a, i| a + i.read().unwrap().height());
+        let form_height = form
+            .items
+            .iter()
+            .fold(0, |a, i| a + i.read().unwrap().height());
 
-            let mut area = layout[1].inner(Margin::new(1, 1));
-            area.height = area.height.min(form_height + form.items.len() as u16 + 1);
-            area.y = area.y.max(layout[1].y + layout[1].height - area.height) + 1;
+        let mut area = layout[1].inner(Margin::new(1, 1));
+        area.height = area
+            .height
+            .min(form_height + form.items.len() as u16 + 1 + header);
+        area.y = area.y.max(layout[1].y + layout[1].height - area.height) + 1;
 
-            form.clone().render(area, buf);
+        match self.wizard {
+            Some(wizard) => wizard.render(area, buf),
+            None => form.clone().render(area, buf),
         }
     }
 }
 
-impl Popup<'_> {
-    pub fn handle_input(&mut self, k: KeyCode) -> bool {
+impl<'a> Popup<'a> {
+    pub fn new(title: impl Into<Line<'a>>, content: impl Into<Text<'a>>) -> Self {
+        Self {
+            title: title.into(),
+            content: content.into(),
+            form: None,
+            wizard: None,
+            theme: None,
+        }
+    }
+
+    pub fn form(mut self, mut form: Form) -> Self {
+        if let Some(theme) = self.theme {
+            form.set_theme(theme);
+        }
+
+        self.form = Some(form);
+        self
+    }
+
+    pub fn wizard(mut self, mut wizard: Wizard) -> Self {
+        if let Some(theme) = self.theme {
+            wizard.set_theme(theme);
+        }
+
+        self.wizard = Some(wizard);
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
         if let Some(form) = self.form.as_mut() {
-            form.handle_input(k)
-        } else {
-            false
+            form.set_theme(theme);
+        }
+
+        if let Some(wizard) = self.wizard.as_mut() {
+            wizard.set_theme(theme);
+        }
+
+        self
+    }
+
+    /// Accepts a full [`KeyEvent`], or a bare [`KeyCode`] for older callers.
+    pub fn handle_input(&mut self, k: impl Into<KeyEvent>) -> FormOutcome {
+        match (self.wizard.as_mut(), self.form.as_mut()) {
+            (Some(wizard), _) => wizard.handle_input(k),
+            (None, Some(form)) => form.handle_input(k),
+            (None, None) => FormOutcome::Continue,
+        }
+    }
+
+    pub fn handle_mouse(&mut self, m: MouseEvent) -> FormOutcome {
+        match (self.wizard.as_mut(), self.form.as_mut()) {
+            (Some(wizard), _) => wizard.handle_mouse(m),
+            (None, Some(form)) => form.handle_mouse(m),
+            (None, None) => FormOutcome::Continue,
         }
     }
 }
 
 //
 
+/// Lets a `dyn FormItem` be downcast back to its concrete type. Implemented
+/// for every `'static` type.
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 #[allow(unused_variables)]
-pub trait FormItem: Send + Sync {
+pub trait FormItem: AsAny + Send + Sync {
     fn focus(&mut self);
     fn blur(&mut self);
     fn ren(&self, a: Rect, b: &mut Buffer);
     fn name(&self) -> String;
-    fn input(&mut self, k: KeyCode) {}
-    fn submit(&self, f: &Form) -> bool {
+    fn input(&mut self, k: KeyEvent) {}
+    /// `a` is the whole area the item was last drawn in, even if part of it
+    /// is scrolled out of view, and `m` is moved into the same coordinates.
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {}
+    /// Runs when the item is pressed (Enter or click). `None` means the item
+    /// doesn't submit; `Some(Err(..))` is shown as the form's error.
+    fn submit(&self, f: &Form) -> Option<Result<(), String>> {
+        None
+    }
+    /// Submitting items are blocked while the form has validation errors.
+    fn is_submit(&self) -> bool {
         false
     }
-    fn value(&self) -> Option<String> {
-        None
+    /// Read-only items are skipped by navigation and ignore input.
+    fn is_read_only(&self) -> bool {
+        false
+    }
+    fn value(&self) -> FormValue {
+        FormValue::Null
+    }
+    /// Replaces the value, returning `false` if `v` doesn't suit this item.
+    fn set_value(&mut self, v: FormValue) -> bool {
+        false
     }
+    /// Restores the value the item was created with.
+    fn reset(&mut self) {}
     fn should_prevent_q(&self) -> bool {
         false
     }
-    fn should_prevent_nav(&self, k: KeyCode) -> bool {
+    /// Keys returning `true` go to the item instead of moving focus. In
+    /// layouts with columns or rows, plain Left / Right move focus unless
+    /// the item claims them; Alt+Left / Right always do. See
+    /// [`is_horizontal_arrow`].
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
         false
     }
     fn height(&self) -> u16 {
         3
     }
+    /// Drawn beside the item by [`FormLayout::LabelLeft`]. Items without one
+    /// keep the full width.
+    fn label(&self) -> String {
+        String::new()
+    }
+    /// Whether to draw the title in the item's own border, which is turned
+    /// off while the form draws [`label`](Self::label) instead.
+    fn set_show_title(&mut self, show: bool) {}
+    /// Whether to draw on one row as `Label: value`, marking focus without
+    /// a border. See [`Form::set_compact`].
+    fn set_compact(&mut self, compact: bool) {}
+    /// Restyles the item. See [`Form::set_theme`].
+    fn set_theme(&mut self, theme: &Theme) {}
 }
 
 #[derive(Default, Clone)]
@@ -113,6 +285,77 @@ pub struct Form {
     pub prevent_q: bool,
     pub title: Option<String>,
     pub borders: bool,
+    /// Keys [`values`](Self::values) and [`slurp`](Self::slurp) as
+    /// `section.name` for items under a [`Section`].
+    pub section_prefix: bool,
+    viewport: Arc<RwLock<Viewport>>,
+    validators: HashMap<String, Vec<Arc<dyn Validator>>>,
+    rules: Vec<Arc<dyn Rule>>,
+    async_validators: HashMap<String, Vec<Arc<dyn AsyncValidator>>>,
+    checks: Arc<RwLock<HashMap<String, AsyncCheck>>>,
+    errors: HashMap<String, String>,
+    touched: HashSet<String>,
+    show_all_errors: bool,
+    listeners: Vec<Sender<FormEvent>>,
+    callbacks: HashMap<String, Vec<FormCallback>>,
+    events: Vec<FormEvent>,
+    message: Option<String>,
+    visible_when: HashMap<String, Condition>,
+    enabled_when: HashMap<String, Condition>,
+    computations: Vec<Computation>,
+    layout: FormLayout,
+    rows: Vec<Vec<String>>,
+    compact: bool,
+    theme: Theme,
+}
+
+/// Where the form was last drawn, so mouse events can be hit-tested
+/// against it.
+#[derive(Default)]
+struct Viewport {
+    area: Rect,
+    hitboxes: Vec<Rect>,
+    /// Where each item sits in the form, scrolled or not.
+    rects: Vec<Rect>,
+    offset: u16,
+    min_offset: u16,
+    max_offset: u16,
+    /// Set when the wheel moved the view; cleared once focus moves again.
+    scrolled_at: Option<usize>,
+}
+
+/// A read guard on a form item, downcast to `T`. See [`Form::get`].
+pub struct ItemRef<'a, T> {
+    guard: RwLockReadGuard<'a, Box<dyn FormItem>>,
+    item: PhantomData<T>,
+}
+
+impl<T: 'static> Deref for ItemRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        (**self.guard).as_any().downcast_ref().unwrap()
+    }
+}
+
+/// A write guard on a form item, downcast to `T`. See [`Form::get_mut`].
+pub struct ItemMut<'a, T> {
+    guard: RwLockWriteGuard<'a, Box<dyn FormItem>>,
+    item: PhantomData<T>,
+}
+
+impl<T: 'static> Deref for ItemMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        (**self.guard).as_any().downcast_ref().unwrap()
+    }
+}
+
+impl<T: 'static> DerefMut for ItemMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        (**self.guard).as_any_mut().downcast_mut().unwrap()
+    }
 }
 
 impl Form {
@@ -120,11 +363,141 @@ impl Form {
         Self::default()
     }
 
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|i| i.read().unwrap().name() == name)
+    }
+
+    /// The item named `name`, if there is one and it's a `T`.
+    pub fn get<T: FormItem>(&self, name: &str) -> Option<ItemRef<'_, T>> {
+        let guard = self.items[self.index_of(name)?].read().unwrap();
+        (**guard).as_any().is::<T>().then_some(ItemRef {
+            guard,
+            item: PhantomData,
+        })
+    }
+
+    /// The item named `name`, if there is one and it's a `T`.
+    pub fn get_mut<T: FormItem>(&mut self, name: &str) -> Option<ItemMut<'_, T>> {
+        let guard = self.items[self.index_of(name)?].write().unwrap();
+        (**guard).as_any().is::<T>().then_some(ItemMut {
+            guard,
+            item: PhantomData,
+        })
+    }
+
+    /// Moves focus to the item named `name`, returning whether it exists.
+    pub fn focus_by_name(&mut self, name: &str) -> bool {
+        match self.index_of(name) {
+            Some(i) => {
+                self.move_focus(i);
+                self.flush_events();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts `item` before the item named `name`, returning whether it
+    /// exists.
+    pub fn insert_before(&mut self, name: &str, item: impl FormItem + 'static) -> bool {
+        match self.index_of(name) {
+            Some(i) => {
+                self.insert(i, item);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Inserts `item` after the item named `name`, returning whether it
+    /// exists.
+    pub fn insert_after(&mut self, name: &str, item: impl FormItem + 'static) -> bool {
+        match self.index_of(name) {
+            Some(i) => {
+                self.insert(i + 1, item);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, i: usize, item: impl FormItem + 'static) {
+        self.items.insert(i, self.wrap(item));
+        if i <= self.focused && self.items.len() > 1 {
+            self.focused += 1;
+        }
+    }
+
+    /// Removes the item named `name`, keeping focus on the same item where
+    /// possible. Its validators, checks, conditions and calculation go with
+    /// it, so an item added later under the same name starts clean; callbacks
+    /// from [`on`](Self::on) stay.
+    ///
+    /// If it had focus, focus moves to the next item that can take it.
+    pub fn remove(&mut self, name: &str) -> Option<Arc<RwLock<Box<dyn FormItem>>>> {
+        let i = self.index_of(name)?;
+        let item = self.items.remove(i);
+        item.write().unwrap().blur();
+
+        self.touched.remove(name);
+        self.validators.remove(name);
+        self.async_validators.remove(name);
+        self.visible_when.remove(name);
+        self.enabled_when.remove(name);
+        self.forget_computation(name);
+        if let Some(check) = self.checks.write().unwrap().remove(name) {
+            check.cancel();
+        }
+
+        if self.items.is_empty() {
+            self.focused = 0;
+        } else if i < self.focused {
+            self.focused -= 1;
+        } else if i == self.focused {
+            let states = self.states();
+            let len = self.items.len();
+            let start = i.min(len - 1);
+            self.focused = (0..len)
+                .map(|n| (start + n) % len)
+                .find(|&j| states[j].can_focus())
+                .unwrap_or(start);
+
+            let mut focused = self.items[self.focused].write().unwrap();
+            focused.focus();
+            self.prevent_q = focused.should_prevent_q();
+            self.events.push(FormEvent::FocusChanged {
+                from: name.to_string(),
+                to: focused.name(),
+            });
+        }
+
+        self.refresh_errors();
+        self.flush_events();
+        Some(item)
+    }
+
     pub fn push(&mut self, item: impl FormItem + 'static) {
-        self.items.push(Arc::new(RwLock::new(Box::new(item))));
+        let item = self.wrap(item);
+        self.items.push(item);
         self.focus(self.items.len());
     }
 
+    fn wrap(&self, mut item: impl FormItem + 'static) -> Arc<RwLock<Box<dyn FormItem>>> {
+        if matches!(self.layout, FormLayout::LabelLeft(_)) {
+            item.set_show_title(false);
+        }
+
+        if self.compact {
+            item.set_compact(true);
+        }
+
+        item.set_theme(&self.theme);
+
+        Arc::new(RwLock::new(Box::new(item)))
+    }
+
     pub fn focus(&mut self, i: usize) {
         if self.items.is_empty() {
             return;
@@ -133,74 +506,525 @@ impl Form {
         let i = i % self.items.len();
         self.items.iter().for_each(|i| i.write().unwrap().blur());
         self.items[i].write().unwrap().focus();
+        self.focused = i;
     }
 
-    pub fn handle_input(&mut self, k: KeyCode) -> bool {
-        if self.items.is_empty() {
-            return false;
-        }
+    /// Attaches `v` to the item named `name`. Its errors are shown once the
+    /// item has been edited or left, or after a blocked submit.
+    pub fn validator(&mut self, name: &str, v: impl Validator + 'static) {
+        self.validators
+            .entry(name.to_string())
+            .or_default()
+            .push(Arc::new(v));
+    }
+
+    /// Attaches a background check to the item named `name`, re-run (and the
+    /// previous run cancelled) whenever its value changes.
+    pub fn async_validator(&mut self, name: &str, v: impl AsyncValidator + 'static) {
+        self.async_validators
+            .entry(name.to_string())
+            .or_default()
+            .push(Arc::new(v));
+    }
 
-        if self.items[self.focused]
+    /// Whether any background check on a shown, enabled item is still
+    /// running.
+    pub fn is_pending(&self) -> bool {
+        let active = self.active_names();
+        self.checks
             .read()
             .unwrap()
-            .should_prevent_nav(k)
+            .iter()
+            .any(|(name, c)| active.contains(name) && c.is_pending())
+    }
+
+    /// Blocks until every background check has finished, or `timeout`
+    /// passes, then shows their errors. Returns whether they all finished.
+    pub fn wait_for_checks(&mut self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        while self.is_pending() && start.elapsed() < timeout {
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        self.refresh_errors();
+        !self.is_pending()
+    }
+
+    /// Adds a check across several items, re-run on every input.
+    pub fn rule(&mut self, r: impl Rule + 'static) {
+        self.rules.push(Arc::new(r));
+    }
+
+    /// Runs every validator and rule, returning `(name, message)` for each
+    /// failure. A [`Group`]'s entries are checked against their own.
+    pub fn validate(&self) -> Vec<(String, String)> {
+        let values = self
+            .visible_values(false)
+            .into_iter()
+            .map(|(name, value)| (name, value.to_string()))
+            .collect::<Vec<_>>();
+        let mut errors = Vec::new();
+        for (name, value) in &values {
+            for v in self.validators.get(name).into_iter().flatten() {
+                if let Err(message) = v.validate(value) {
+                    errors.push((name.clone(), message));
+                }
+            }
+        }
+
+        let checks = self.checks.read().unwrap();
+        for (name, value) in &values {
+            if let Some(message) = checks
+                .get(name)
+                .filter(|c| c.value == *value)
+                .and_then(|c| c.error())
+            {
+                errors.push((name.clone(), message));
+            }
+        }
+
+        let values = values.into_iter().collect::<HashMap<_, _>>();
+        for rule in &self.rules {
+            if let Err(e) = rule.check(&values) {
+                errors.extend(e.names.into_iter().map(|n| (n, e.message.clone())));
+            }
+        }
+
+        for item in &self.items {
+            let item = item.read().unwrap();
+            if let Some(group) = (**item).as_any().downcast_ref::<Group>() {
+                let name = group.name.clone();
+                errors.extend(group.validate().into_iter().map(|m| (name.clone(), m)));
+            }
+        }
+
+        // Hidden and disabled items can't be fixed, so they never block.
+        let active = self.active_names();
+        errors.retain(|(name, _)| active.contains(name));
+        errors
+    }
+
+    /// Names of the items that are shown and enabled.
+    fn active_names(&self) -> HashSet<String> {
+        self.items
+            .iter()
+            .zip(self.states())
+            .filter(|(_, s)| s.is_active())
+            .map(|(i, _)| i.read().unwrap().name())
+            .collect()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_empty()
+    }
+
+    /// Starts checks on changed values, and drops those on items that have
+    /// since been hidden, disabled or removed.
+    fn start_checks(&mut self) {
+        let active = self.active_names();
+        let mut checks = self.checks.write().unwrap();
+        checks.retain(|name, c| {
+            if !active.contains(name) {
+                c.cancel();
+            }
+
+            active.contains(name)
+        });
+
+        for (name, value) in self.visible_values(false) {
+            if !active.contains(&name) {
+                continue;
+            }
+
+            let value = value.to_string();
+            let Some(validators) = self.async_validators.get(&name) else {
+                continue;
+            };
+
+            if checks.get(&name).is_some_and(|c| c.value == value) {
+                continue;
+            }
+
+            if let Some(old) = checks.insert(name, AsyncCheck::spawn(validators.clone(), &value)) {
+                old.cancel();
+            }
+        }
+    }
+
+    fn refresh_errors(&mut self) {
+        self.start_checks();
+        self.errors.clear();
+        for (name, message) in self.validate() {
+            if self.show_all_errors || self.touched.contains(&name) {
+                self.errors.entry(name).or_insert(message);
+            }
+        }
+    }
+
+    fn touch(&mut self, i: usize) {
+        let name = self.items[i].read().unwrap().name();
+        self.touched.insert(name);
+    }
+
+    /// Whether submitting has to wait for pending checks or fixes, in which
+    /// case every error is shown.
+    fn blocked(&mut self) -> bool {
+        self.start_checks();
+        if self.is_pending() || !self.is_valid() {
+            self.show_all_errors = true;
+            self.refresh_errors();
+            return true;
+        }
+
+        false
+    }
+
+    /// Presses item `i`, or returns `None` if it doesn't submit.
+    fn try_submit(&mut self, i: usize) -> Option<FormOutcome> {
+        let item = self.items[i].clone();
+        let item = item.read().unwrap();
+        if item.is_submit() && self.blocked() {
+            return Some(FormOutcome::Continue);
+        }
+
+        Some(match item.submit(self)? {
+            Ok(()) if item.is_submit() => FormOutcome::Submitted(self.values()),
+            Ok(()) => FormOutcome::Action(item.name()),
+            Err(message) => {
+                self.message = Some(message.clone());
+                FormOutcome::Error(message)
+            }
+        })
+    }
+
+    /// Presses the first submitting item, or submits directly if there's
+    /// none.
+    fn submit_form(&mut self) -> FormOutcome {
+        // Settles what's being typed (e.g. clamps a number) before it's read.
+        let focused = self.items[self.focused].clone();
+        focused.write().unwrap().blur();
+        focused.write().unwrap().focus();
+
+        match self.first_submit() {
+            Some(i) => self.try_submit(i).unwrap_or_default(),
+            None if self.blocked() => FormOutcome::Continue,
+            None => FormOutcome::Submitted(self.values()),
+        }
+    }
+
+    /// The first shown, enabled submitting item, which Ctrl+S presses.
+    pub(super) fn first_submit(&self) -> Option<usize> {
+        self.items
+            .iter()
+            .zip(self.states())
+            .position(|(i, s)| s.is_active() && i.read().unwrap().is_submit())
+    }
+
+    fn move_focus(&mut self, i: usize) {
+        let from = self.focused;
+        self.touch(self.focused);
+        self.items[self.focused].write().unwrap().blur();
+        self.focused = i % self.items.len();
+        self.items[self.focused].write().unwrap().focus();
+        if from != self.focused {
+            self.events.push(FormEvent::FocusChanged {
+                from: self.items[from].read().unwrap().name(),
+                to: self.items[self.focused].read().unwrap().name(),
+            });
+        }
+
+        self.prevent_q = self.items[self.focused].read().unwrap().should_prevent_q();
+    }
+
+    /// Accepts a full [`KeyEvent`], or a bare [`KeyCode`] for older callers.
+    ///
+    /// Release events are ignored, so terminals that report both press and
+    /// release (e.g. Windows) don't double up input. Esc cancels and Ctrl+S
+    /// submits from anywhere in the form.
+    pub fn handle_input(&mut self, k: impl Into<KeyEvent>) -> FormOutcome {
+        let before = self.snapshot();
+        let outcome = self.key(k.into());
+        self.finish(before, outcome)
+    }
+
+    /// Clicks focus (and press buttons) under the pointer, drags go to the
+    /// focused item, and the wheel scrolls the form.
+    pub fn handle_mouse(&mut self, m: MouseEvent) -> FormOutcome {
+        let before = self.snapshot();
+        let outcome = self.mouse(m);
+        self.finish(before, outcome)
+    }
+
+    fn key(&mut self, mut k: KeyEvent) -> FormOutcome {
+        if self.items.is_empty() || k.kind == KeyEventKind::Release {
+            return FormOutcome::Continue;
+        }
+
+        self.message = None;
+        if k.code == KeyCode::Tab && k.modifiers.contains(KeyModifiers::SHIFT) {
+            k.code = KeyCode::BackTab;
+        }
+
+        // Ctrl+Alt+S is AltGr+S on Windows, which types rather than submits.
+        if k.code == KeyCode::Char('s')
+            && k.modifiers.contains(KeyModifiers::CONTROL)
+            && !k.modifiers.contains(KeyModifiers::ALT)
+        {
+            return self.submit_form();
+        }
+
+        let active = self.states()[self.focused].can_focus();
+        if active
+            && self.items[self.focused]
+                .read()
+                .unwrap()
+                .should_prevent_nav(k)
         {
             self.items[self.focused].write().unwrap().input(k);
-            return false;
+            self.touch(self.focused);
+            self.recompute();
+            self.settle_focus();
+            self.refresh_errors();
+            return FormOutcome::Continue;
         }
 
-        match k {
+        let sideways = match k.code {
+            KeyCode::Left => self.neighbour(Nav::Left),
+            KeyCode::Right => self.neighbour(Nav::Right),
+            _ => None,
+        };
+
+        match k.code {
+            KeyCode::Esc => return FormOutcome::Cancelled,
+            KeyCode::Tab => self.step_focus(true),
+            KeyCode::BackTab => self.step_focus(false),
+            KeyCode::Down => match self.neighbour(Nav::Down) {
+                Some(i) => self.move_focus(i),
+                None => self.step_focus(true),
+            },
+            KeyCode::Up => match self.neighbour(Nav::Up) {
+                Some(i) => self.move_focus(i),
+                None => self.step_focus(false),
+            },
+            KeyCode::Left | KeyCode::Right if sideways.is_some() => {
+                self.move_focus(sideways.unwrap_or(self.focused))
+            }
+            _ if !active => {}
             KeyCode::Enter => {
-                if self.items[self.focused].read().unwrap().submit(self) {
-                    return true;
+                if let Some(outcome) = self.try_submit(self.focused) {
+                    return outcome;
                 }
 
                 self.items[self.focused].write().unwrap().input(k);
             }
-            KeyCode::Tab | KeyCode::Down => {
-                self.items[self.focused].write().unwrap().blur();
-                self.focused = (self.focused + 1) % self.items.len();
-                self.items[self.focused].write().unwrap().focus();
-                self.prevent_q = self.items[self.focused].read().unwrap().should_prevent_q();
-            }
-            KeyCode::BackTab | KeyCode::Up => {
-                self.items[self.focused].write().unwrap().blur();
-                self.focused = (self.focused + self.items.len() - 1) % self.items.len();
-                self.items[self.focused].write().unwrap().focus();
-                self.prevent_q = self.items[self.focused].read().unwrap().should_prevent_q();
-            }
             _ => {
                 self.items[self.focused].write().unwrap().input(k);
+                self.touch(self.focused);
                 self.prevent_q = self.items[self.focused].read().unwrap().should_prevent_q();
             }
         }
 
-        false
+        self.recompute();
+        self.settle_focus();
+        self.refresh_errors();
+        FormOutcome::Continue
     }
 
-    pub fn slurp(&self) -> Vec<(String, String)> {
+    fn mouse(&mut self, m: MouseEvent) -> FormOutcome {
+        if self.items.is_empty() {
+            return FormOutcome::Continue;
+        }
+
+        if matches!(m.kind, MouseEventKind::Down(_)) {
+            self.message = None;
+        }
+
+        let position = Position::new(m.column, m.row);
+        let hit = {
+            let mut viewport = self.viewport.write().unwrap();
+            if !viewport.area.contains(position)
+                && matches!(
+                    m.kind,
+                    MouseEventKind::Down(_) | MouseEventKind::ScrollUp | MouseEventKind::ScrollDown
+                )
+            {
+                return FormOutcome::Continue;
+            }
+
+            match m.kind {
+                MouseEventKind::ScrollDown => {
+                    viewport.offset = (viewport.offset + 1).min(viewport.max_offset);
+                    viewport.scrolled_at = Some(self.focused);
+                    return FormOutcome::Continue;
+                }
+                MouseEventKind::ScrollUp => {
+                    viewport.offset = viewport.offset.saturating_sub(1).max(viewport.min_offset);
+                    viewport.scrolled_at = Some(self.focused);
+                    return FormOutcome::Continue;
+                }
+                _ => {}
+            }
+
+            viewport.hitboxes.iter().position(|r| r.contains(position))
+        };
+
+        match m.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(i) = hit.filter(|i| self.states()[*i].can_focus()) else {
+                    return FormOutcome::Continue;
+                };
+
+                if i != self.focused {
+                    self.move_focus(i);
+                }
+
+                let Some((m, area)) = self.local(i, m) else {
+                    return FormOutcome::Continue;
+                };
+
+                self.items[i].write().unwrap().mouse(m, area);
+                self.touch(i);
+                if let Some(outcome) = self.try_submit(i) {
+                    return outcome;
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) | MouseEventKind::Up(MouseButton::Left) => {
+                if let Some((m, area)) = self.local(self.focused, m) {
+                    self.items[self.focused].write().unwrap().mouse(m, area);
+                }
+            }
+            _ => {}
+        }
+
+        self.recompute();
+        self.settle_focus();
+        self.refresh_errors();
+        self.prevent_q = self.items[self.focused].read().unwrap().should_prevent_q();
+        FormOutcome::Continue
+    }
+
+    /// Item `i`'s whole rect and `m`, both in the coordinates it was drawn
+    /// in (inside the form's scrolled content), so the item can work out
+    /// positions even when it's partly scrolled off.
+    fn local(&self, i: usize, mut m: MouseEvent) -> Option<(MouseEvent, Rect)> {
+        let viewport = self.viewport.read().unwrap();
+        m.column = m.column.saturating_sub(viewport.area.x);
+        m.row = (m.row + viewport.offset).saturating_sub(viewport.area.y);
+        Some((m, *viewport.rects.get(i)?))
+    }
+
+    /// Sets items by name, returning the names that didn't exist or rejected
+    /// their value. Changes are reported as from input.
+    pub fn fill<K: AsRef<str>, V: Into<FormValue>>(
+        &mut self,
+        values: impl IntoIterator<Item = (K, V)>,
+    ) -> Vec<String> {
+        let before = self.snapshot();
+        let mut rejected = Vec::new();
+        for (name, value) in values {
+            let name = name.as_ref();
+            let item = self.items.iter().find(|i| i.read().unwrap().name() == name);
+
+            if !item.is_some_and(|i| i.write().unwrap().set_value(value.into())) {
+                rejected.push(name.to_string());
+            }
+        }
+
+        self.recompute();
+        self.settle_focus();
+        self.refresh_errors();
+        self.finish(before, FormOutcome::Continue);
+        rejected
+    }
+
+    /// Restores every item's initial value and hides any errors. Changes are
+    /// reported as from input.
+    pub fn reset(&mut self) {
+        let before = self.snapshot();
+        self.items.iter().for_each(|i| i.write().unwrap().reset());
+        self.touched.clear();
+        self.show_all_errors = false;
+        self.recompute();
+        self.settle_focus();
+        self.refresh_errors();
+        self.finish(before, FormOutcome::Continue);
+    }
+
+    /// Every visible item's typed value, by name.
+    pub fn values(&self) -> HashMap<String, FormValue> {
+        self.visible_values(self.section_prefix)
+            .into_iter()
+            .collect()
+    }
+
+    /// Visible items' values in order, leaving out section headers and
+    /// buttons, which don't hold one. Names are prefixed by their section
+    /// when `prefixed`.
+    pub(super) fn visible_values(&self, prefixed: bool) -> Vec<(String, FormValue)> {
+        let sections = self.sections();
+        self.items
+            .iter()
+            .zip(self.states())
+            .zip(&sections)
+            .filter(|((i, s), _)| {
+                let item = i.read().unwrap();
+                let item = (**item).as_any();
+                s.visible && !item.is::<Section>() && !item.is::<Button<'static>>()
+            })
+            .map(|((i, _), section)| {
+                let i = i.read().unwrap();
+                let name = match section {
+                    Some(s) if prefixed => {
+                        format!("{}.{}", self.items[*s].read().unwrap().name(), i.name())
+                    }
+                    _ => i.name(),
+                };
+
+                (name, i.value())
+            })
+            .collect()
+    }
+
+    /// Every item's typed value, hidden ones included.
+    fn all_values(&self) -> HashMap<String, FormValue> {
         self.items
             .iter()
             .map(|i| {
                 let i = i.read().unwrap();
-                (i.name(), i.value().unwrap_or_default())
+                (i.name(), i.value())
             })
+            .collect()
+    }
+
+    /// [`values`](Self::values) as strings, in item order.
+    pub fn slurp(&self) -> Vec<(String, String)> {
+        self.visible_values(self.section_prefix)
+            .into_iter()
+            .map(|(name, value)| (name, value.to_string()))
             .collect::<Vec<_>>()
     }
 
+    /// Rows needed to draw every shown item without scrolling.
+    pub fn content_height(&self) -> u16 {
+        self.arrange(0, &self.states()).1
+    }
+
     pub fn ren(&self, area: Rect, buf: &mut Buffer) {
-        let heights = self
-            .items
-            .iter()
-            .map(|i| i.clone().read().unwrap().height());
-        let height = heights.clone().sum::<u16>() + (self.items.len()).saturating_sub(1) as u16;
+        let states = self.states();
+        let shown = (0..self.items.len())
+            .filter(|i| states[*i].is_shown())
+            .collect::<Vec<_>>();
+        let (slots, height) = self.arrange(area.width - 2, &states);
 
         Block::new()
             .borders(match self.borders {
                 true => Borders::ALL,
                 false => Borders::NONE,
             })
+            .border_set(self.theme.glyphs().border_set(self.theme.border_type))
+            .border_style(self.theme.unfocused)
             .title(self.title.clone().unwrap_or_default())
+            .title_style(self.theme.title)
             .render(area, buf);
 
         let border_offset = match self.borders {
@@ -215,46 +1039,126 @@ impl Form {
             height,
         };
 
+        let inner = Rect {
+            x: area.x + border_offset.0,
+            y: area.y + border_offset.1,
+            width: area.width - (border_offset.0 * 2),
+            height: area.height - (border_offset.1 * 2),
+        };
+
         let mut scroll = Scroll::new(max_size).page_size(area);
         let mut scroll_offset = 0;
-        let layout = layout::Layout::new(
-            layout::Direction::Vertical,
-            self.items
-                .iter()
-                .map(|i| Constraint::Length(i.clone().read().unwrap().height()))
-                .collect::<Vec<_>>(),
-        )
-        .spacing(1)
-        .flex(Flex::SpaceBetween)
-        .split(max_size);
+        // Hidden items keep an empty rect, so indices still line up.
+        let layout = slots.iter().map(|s| s.item).collect::<Vec<_>>();
 
-        for (i, item) in self.items.iter().enumerate() {
-            let widget = item.read().unwrap();
+        let checks = self.checks.read().unwrap();
+        let mut errors = None;
+        for &i in &shown {
+            let widget = self.items[i].read().unwrap();
             if self.focused == i {
-                let feet_y =
-                    heights.clone().take(i).sum::<u16>() + widget.height() as u16 + i as u16 + 3;
+                let feet_y = layout[i].bottom() + 2;
                 if feet_y > area.height {
                     scroll_offset = feet_y - area.height;
                 }
             }
 
             widget.ren(layout[i], scroll.buffer_mut());
+            if let Some(label) = slots[i].label {
+                let style = match self.focused == i {
+                    true => self.theme.selection,
+                    false => self.theme.title,
+                };
+
+                Paragraph::new(widget.label())
+                    .style(style)
+                    .render(label, scroll.buffer_mut());
+            }
+
+            if !states[i].enabled {
+                for area in [Some(layout[i]), slots[i].label].into_iter().flatten() {
+                    scroll.buffer_mut().set_style(area, self.theme.disabled);
+                }
+            }
+
+            if let Some(section) = (**widget).as_any().downcast_ref::<Section>() {
+                if section.collapsed && section.show_invalid_count {
+                    let errors = errors.get_or_insert_with(|| self.validate());
+                    let count = self.invalid_in_section(i, errors);
+                    render_invalid_count(
+                        layout[i],
+                        scroll.buffer_mut(),
+                        &section.title,
+                        count,
+                        self.theme.invalid,
+                    );
+                }
+            }
+
+            let name = widget.name();
+            let check = checks.get(&name);
+            let message = self.errors.get(&name).cloned().or_else(|| {
+                (self.show_all_errors || self.touched.contains(&name))
+                    .then(|| check.and_then(|c| c.error()))
+                    .flatten()
+            });
+
+            if let Some(message) = message {
+                render_error(layout[i], scroll.buffer_mut(), &message, self.theme.invalid);
+            }
+
+            if check.is_some_and(|c| c.is_pending()) {
+                render_pending(layout[i], scroll.buffer_mut(), &self.theme);
+            }
         }
 
-        scroll.set_offset(Position {
-            y: scroll_offset,
-            x: 0,
-        });
+        let mut viewport = self.viewport.write().unwrap();
+        viewport.min_offset = max_size.y;
+        viewport.max_offset = max_size
+            .bottom()
+            .saturating_sub(inner.height)
+            .max(max_size.y);
+        if viewport.scrolled_at == Some(self.focused) {
+            scroll_offset = viewport.offset;
+        } else {
+            viewport.scrolled_at = None;
+        }
 
-        scroll.render(
-            Rect {
-                x: area.x + border_offset.0,
-                y: area.y + border_offset.1,
-                width: area.width - (border_offset.0 * 2),
-                height: area.height - (border_offset.1 * 2),
-            },
-            buf,
-        );
+        let top = scroll_offset.clamp(viewport.min_offset, viewport.max_offset);
+        viewport.offset = top;
+        viewport.area = inner;
+        viewport.rects = layout.clone();
+        viewport.hitboxes = layout
+            .iter()
+            .map(|r| {
+                let y = (inner.y + r.y) as i32 - top as i32;
+                Rect {
+                    x: inner.x + r.x,
+                    y: y.max(inner.y as i32) as u16,
+                    width: r.width,
+                    height: r.height.saturating_sub((inner.y as i32 - y).max(0) as u16),
+                }
+                .intersection(inner)
+            })
+            .collect();
+
+        scroll.set_offset(Position { y: top, x: 0 });
+
+        scroll.render(inner, buf);
+
+        if let Some(message) = &self.message {
+            match self.borders {
+                true => render_error(area, buf, message, self.theme.invalid),
+                false => {
+                    buf.set_stringn(
+                        area.x,
+                        area.bottom().saturating_sub(1),
+                        message,
+                        area.width as usize,
+                        self.theme.invalid,
+                    );
+                }
+            }
+        }
     }
 }
 
@@ -269,9 +1173,11 @@ impl Widget for Form {
 
 //
 
+pub type TitledRenderer = Box<dyn Fn(Rect, &mut Buffer)>;
+
 pub struct Titled {
     pub title: String,
-    pub widget: Box<dyn Fn(Rect, &mut Buffer)>,
+    pub widget: TitledRenderer,
 }
 
 impl Titled {
@@ -306,6 +1212,7 @@ impl Titled {
         Block::new()
             .title(title)
             .borders(Borders::ALL)
+            .border_set(glyphs().border_set(BorderType::Plain))
             .render(area, buf);
 
         widget.render(l1[1], buf);
@@ -340,8 +1247,134 @@ impl Widget for Titled {
         Block::new()
             .title(self.title)
             .borders(Borders::ALL)
+            .border_set(glyphs().border_set(BorderType::Plain))
             .render(area, buf);
 
         (self.widget)(l1[1], buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::{buffer::Buffer, layout::Rect};
+
+    use super::*;
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// Each row of `buf`, as text.
+    pub(super) fn screen(buf: &Buffer) -> Vec<String> {
+        let area = buf.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn clicks_reach_items_scrolled_partly_out_of_view() {
+        let mut form = Form::new();
+        form.push(Input::new("a", "A", ""));
+        let values = vec!["x".to_string(), "y".to_string(), "z".to_string()];
+        form.push(List::new("l", "L", values));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 6));
+        form.ren(buf.area, &mut buf);
+        for _ in 0..5 {
+            form.handle_mouse(MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                ..click(1, 1)
+            });
+        }
+
+        buf.reset();
+        form.ren(buf.area, &mut buf);
+        let rows = screen(&buf);
+        assert!(rows[0].contains('x'));
+        assert!(rows[1].contains('y'));
+
+        // The list's top border is scrolled off, so its hitbox starts a row
+        // below the rect its values are laid out in.
+        let column = rows[1].chars().position(|c| c == 'y').unwrap() as u16;
+        form.handle_mouse(click(column, 1));
+        form.handle_input(KeyCode::Delete);
+        assert_eq!(
+            form.values()["l"],
+            FormValue::List(vec!["x".to_string(), "z".to_string()])
+        );
+    }
+
+    #[test]
+    fn removing_the_focused_item_settles_focus() {
+        let mut form = Form::new();
+        form.push(Input::new("a", "A", ""));
+        form.push(Input::new("b", "B", ""));
+        form.push(Computed::new("c", "C"));
+        form.push(Input::new("d", "D", ""));
+        form.focus_by_name("b");
+
+        let events = form.subscribe();
+        form.remove("b");
+        assert_eq!(form.items[form.focused].read().unwrap().name(), "d");
+        assert!(form.get::<Input>("d").unwrap().focused);
+        assert_eq!(
+            events.try_iter().collect::<Vec<_>>(),
+            vec![FormEvent::FocusChanged {
+                from: "b".to_string(),
+                to: "d".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn items_readded_under_a_removed_name_start_clean() {
+        let mut form = Form::new();
+        form.push(Input::new("a", "A", ""));
+        form.push(Input::new("b", "B", ""));
+        form.validator("b", Required);
+        form.visible_when("b", |_| false);
+        form.compute("b", &["a"], |_| "computed".into()).unwrap();
+
+        form.remove("b");
+        form.push(Input::new("b", "B", ""));
+        form.handle_input(KeyCode::Char('x'));
+
+        assert!(form.is_valid());
+        assert!(form.is_visible("b"));
+        assert_eq!(form.values()["b"], FormValue::Text(String::new()));
+    }
+
+    #[test]
+    fn clicks_on_a_lists_border_select_nothing() {
+        let mut form = Form::new();
+        let values = vec!["x".to_string(), "y".to_string()];
+        form.push(List::new("l", "L", values));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 30, 6));
+        form.ren(buf.area, &mut buf);
+        let rows = screen(&buf);
+        assert!(rows[1].contains('x'));
+
+        // Top border, then bottom border: neither picks a value to delete.
+        for row in [0, 5] {
+            form.handle_mouse(click(2, row));
+            form.handle_input(KeyCode::Delete);
+        }
+
+        assert_eq!(
+            form.values()["l"],
+            FormValue::List(vec!["x".to_string(), "y".to_string()])
+        );
+    }
+}
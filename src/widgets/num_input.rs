@@ -1,36 +1,66 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    text::Line,
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget},
 };
 
-use super::{add_cursor, FormItem};
+use super::{
+    has_command_modifier, is_horizontal_arrow, render_compact_label, shown_title, value_area,
+    FormItem, FormValue, TextBuffer, Theme,
+};
 
 #[derive(Clone)]
 pub struct NumInput {
     pub name: String,
     pub title: String,
-    pub value: String,
+    pub value: TextBuffer,
     pub focused: bool,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
     pub min: i64,
     pub max: i64,
     pub step: i64,
-    cursor: usize,
+    initial: i64,
 }
 
 impl NumInput {
     pub fn new(name: &str, title: &str, value: i64, range: (i64, i64), step: i64) -> Self {
         Self {
             title: title.to_string(),
-            value: value.to_string(),
+            value: TextBuffer::new(&value.to_string()),
             focused: false,
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
             min: range.0,
             max: range.1,
             step,
-            cursor: 0,
             name: name.to_string(),
+            initial: value,
+        }
+    }
+
+    /// Draws on one row as `Title: value`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
+        }
+    }
+
+    /// The typed-in number, if the text is one.
+    fn parsed(&self) -> Option<i64> {
+        self.value.as_str().trim().parse().ok()
+    }
+
+    /// Clamps the typed-in number to the range, or clears text that isn't
+    /// a number (e.g. a lone `-`).
+    fn commit(&mut self) {
+        match self.parsed() {
+            Some(n) => self.value.set(&n.clamp(self.min, self.max).to_string()),
+            None => self.value.clear(),
         }
     }
 }
@@ -44,73 +74,107 @@ impl FormItem for NumInput {
         self.clone().render(a, b);
     }
 
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
+
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
+        is_horizontal_arrow(&k)
+    }
+
     fn focus(&mut self) {
         self.focused = true;
     }
 
     fn blur(&mut self) {
         self.focused = false;
-        self.value = self
-            .value
-            .trim()
-            .parse::<i64>()
-            .unwrap_or(0)
-            .max(self.min)
-            .min(self.max)
-            .to_string();
-    }
-
-    fn value(&self) -> Option<String> {
-        Some(self.value.clone())
-    }
-
-    fn input(&mut self, k: KeyCode) {
-        match k {
-            KeyCode::Backspace => {
-                if self.cursor > 0 {
-                    self.value.remove(self.cursor - 1);
-                    self.cursor -= 1;
-                }
-            }
-            KeyCode::Left => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
+        self.commit();
+    }
+
+    /// The number as typed, or `Null` while the field is empty or not yet a
+    /// number. It's clamped to the range on blur or Enter.
+    fn value(&self) -> FormValue {
+        self.parsed().map(FormValue::Int).unwrap_or(FormValue::Null)
+    }
+
+    /// Numbers (and numeric text) are clamped to the range; `Null` clears
+    /// the field.
+    fn set_value(&mut self, v: FormValue) -> bool {
+        let n = match v {
+            FormValue::Null => {
+                self.value.clear();
+                return true;
             }
-            KeyCode::Right => {
-                if self.cursor < self.value.len() {
-                    self.cursor += 1;
+            FormValue::Int(i) => i,
+            FormValue::Float(f) => f.round() as i64,
+            FormValue::Text(s) => match s.trim().parse() {
+                Ok(i) => i,
+                Err(_) => return false,
+            },
+            _ => return false,
+        };
+
+        self.value.set(&n.clamp(self.min, self.max).to_string());
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value.set(&self.initial.to_string());
+    }
+
+    fn input(&mut self, k: KeyEvent) {
+        let command = has_command_modifier(&k);
+        match k.code {
+            KeyCode::Char(c) if c.is_ascii_digit() && !command => self.value.insert(c),
+            KeyCode::Char('-') if self.min < 0 && !command => {
+                let cursor = self.value.cursor();
+                match self.value.as_str().strip_prefix('-') {
+                    Some(rest) => {
+                        self.value = TextBuffer::new(rest);
+                        self.value.set_cursor(cursor.saturating_sub(1));
+                    }
+                    None => {
+                        self.value = TextBuffer::new(&format!("-{}", self.value));
+                        self.value.set_cursor(cursor + 1);
+                    }
                 }
             }
-            KeyCode::Home => {
-                self.cursor = 0;
-            }
-            KeyCode::End => {
-                self.cursor = self.value.len();
+            KeyCode::Char(_) if !command => {}
+            KeyCode::Enter => self.commit(),
+            _ => {
+                self.value.input(k);
             }
-            KeyCode::Delete => {
-                self.value.remove(self.cursor);
-            }
-            KeyCode::Char(c) => {
-                if c.is_digit(10) {
-                    self.value.insert(self.cursor, c);
-                    self.cursor += 1;
-                }
+        }
+    }
 
-                if c == '-' && self.min < 0 {
-                    self.value = match self.value.starts_with('-') {
-                        true => {
-                            self.cursor = self.cursor.saturating_sub(1);
-                            self.value[1..].to_string()
-                        }
-                        false => {
-                            self.cursor = self.cursor.saturating_add(1).min(self.value.len() + 1);
-                            format!("-{}", self.value)
-                        }
-                    };
-                }
-            }
-            _ => {}
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {
+        if m.kind == MouseEventKind::Down(MouseButton::Left) {
+            let label = shown_title(self.show_title, &self.title);
+
+            let area = value_area(a, label, self.compact);
+            self.value.place_cursor(
+                m.column.saturating_sub(area.x) as usize,
+                area.width as usize,
+            );
         }
     }
 }
@@ -123,31 +187,78 @@ where
     where
         Self: Sized,
     {
-        let mut offset = 0;
-        let slice = match area.columns().count() < self.value.len() + 3
-            && self.cursor > area.columns().count()
-        {
-            false => self.value.clone(),
-            true => {
-                // slice with the cursor at the end
-                offset = self.cursor - area.columns().count() + 3;
-                self.value[offset..].to_string()
-            }
-        };
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
 
-        let block = Block::default()
-            .title(self.title)
-            .borders(Borders::ALL)
-            .border_style(match self.focused {
-                true => ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
-                false => ratatui::style::Style::default(),
-            });
-
-        Paragraph::new(match self.focused {
-            true => add_cursor(slice, self.cursor - offset),
-            false => Line::raw(self.value.clone()),
-        })
+            let area = render_compact_label(area, buf, label, self.focused, &self.theme);
+            Paragraph::new(self.value.line(
+                area.width as usize,
+                self.focused.then_some(self.theme.cursor),
+            ))
+            .render(area, buf);
+            return;
+        }
+
+        let block = self.theme.block(
+            shown_title(self.show_title, &self.title).to_string(),
+            self.focused,
+        );
+
+        Paragraph::new(self.value.line(
+            area.width.saturating_sub(2) as usize,
+            self.focused.then_some(self.theme.cursor),
+        ))
         .block(block)
         .render(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::widgets::{Form, Required};
+
+    fn typed(n: &mut NumInput, s: &str) {
+        n.value.clear();
+        s.chars()
+            .for_each(|c| n.input(KeyEvent::from(KeyCode::Char(c))));
+    }
+
+    #[test]
+    fn partial_text_is_null() {
+        let mut n = NumInput::new("n", "N", 5, (-10, 10), 1);
+        typed(&mut n, "");
+        assert_eq!(n.value(), FormValue::Null);
+        typed(&mut n, "-");
+        assert_eq!(n.value(), FormValue::Null);
+        typed(&mut n, "-4");
+        assert_eq!(n.value(), FormValue::Int(-4));
+    }
+
+    #[test]
+    fn clamps_on_blur_and_enter() {
+        let mut n = NumInput::new("n", "N", 5, (0, 10), 1);
+        typed(&mut n, "42");
+        assert_eq!(n.value(), FormValue::Int(42));
+        n.input(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(n.value(), FormValue::Int(10));
+
+        typed(&mut n, "");
+        n.blur();
+        assert_eq!(n.value(), FormValue::Null);
+    }
+
+    #[test]
+    fn required_fails_when_empty() {
+        let mut form = Form::new();
+        form.push(NumInput::new("port", "Port", 80, (1, 65535), 1));
+        form.validator("port", Required);
+        assert!(form.is_valid());
+
+        form.fill([("port", FormValue::Null)]);
+        assert!(!form.is_valid());
+        assert_eq!(form.values()["port"], FormValue::Null);
+    }
+}
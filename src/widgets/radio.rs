@@ -1,15 +1,22 @@
-use std::ops::{Add, Sub};
+use std::{
+    ops::{Add, Sub},
+    rc::Rc,
+};
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{self, Alignment, Constraint, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget},
 };
+use unicode_width::UnicodeWidthStr;
 
-use super::FormItem;
+use super::{
+    compact_split, is_horizontal_arrow, render_compact_label, shown_title, FormItem, FormValue,
+    Theme,
+};
 
 #[derive(Clone)]
 pub struct Radio {
@@ -18,6 +25,10 @@ pub struct Radio {
     pub value: String,
     pub options: Vec<(String, String)>,
     pub focused: bool,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
+    initial: String,
 }
 
 impl Radio {
@@ -32,6 +43,18 @@ impl Radio {
                 .collect::<Vec<_>>(),
             value: value.to_string(),
             focused: false,
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
+            initial: value.to_string(),
+        }
+    }
+
+    /// Draws on one row as `Title: value`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
         }
     }
 
@@ -41,6 +64,55 @@ impl Radio {
             .position(|(_, a)| a == &self.value)
             .unwrap_or(0) as isize
     }
+
+    /// The option's label, bracketed when chosen if the theme is monochrome.
+    fn option_text(&self, label: &str, chosen: bool) -> String {
+        match (self.theme.monochrome, chosen) {
+            (true, true) => format!("[{label}]"),
+            (true, false) => format!(" {label} "),
+            (false, _) => label.to_string(),
+        }
+    }
+
+    fn option_areas(&self, area: Rect) -> Rc<[Rect]> {
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
+
+            let area = compact_split(area, label).1;
+            let mut x = area.x;
+            return self
+                .options
+                .iter()
+                .map(|(l, _)| {
+                    let width = self.option_text(l, false).width() as u16;
+                    let r = Rect {
+                        x,
+                        width: width.min(area.right().saturating_sub(x)),
+                        ..area
+                    };
+                    x = (x + width + 2).min(area.right());
+                    r
+                })
+                .collect();
+        }
+
+        let inner_0 = Layout::new(
+            layout::Direction::Vertical,
+            vec![
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(1),
+            ],
+        )
+        .split(area);
+
+        Layout::new(
+            layout::Direction::Horizontal,
+            self.options.iter().map(|_| Constraint::Fill(1)),
+        )
+        .flex(layout::Flex::SpaceBetween)
+        .split(inner_0[1])
+    }
 }
 
 impl FormItem for Radio {
@@ -59,12 +131,39 @@ impl FormItem for Radio {
         self.clone().render(a, b);
     }
 
-    fn input(&mut self, k: KeyCode) {
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
+
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
+        is_horizontal_arrow(&k)
+    }
+
+    fn input(&mut self, k: KeyEvent) {
         if self.options.is_empty() {
             return;
         }
 
-        match k {
+        match k.code {
             KeyCode::Left => {
                 self.value = self.options[(self.get_index_of_selected().sub(1)
                     % self.options.len() as isize)
@@ -84,8 +183,37 @@ impl FormItem for Radio {
         }
     }
 
-    fn value(&self) -> Option<String> {
-        Some(self.value.clone())
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {
+        if m.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+
+        if let Some(i) = self
+            .option_areas(a)
+            .iter()
+            .position(|r| r.left() <= m.column && m.column < r.right())
+        {
+            self.value = self.options[i].1.clone();
+        }
+    }
+
+    fn value(&self) -> FormValue {
+        FormValue::Choice(self.value.clone())
+    }
+
+    /// Only accepts the value of one of the options.
+    fn set_value(&mut self, v: FormValue) -> bool {
+        let v = v.to_string();
+        if !self.options.iter().any(|(_, o)| *o == v) {
+            return false;
+        }
+
+        self.value = v;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = self.initial.clone();
     }
 }
 
@@ -94,40 +222,29 @@ impl Widget for Radio {
     where
         Self: Sized,
     {
-        let inner_0 = Layout::new(
-            layout::Direction::Vertical,
-            vec![
-                Constraint::Length(1),
-                Constraint::Fill(1),
-                Constraint::Length(1),
-            ],
-        )
-        .split(area);
+        let inner_1 = self.option_areas(area);
 
-        let inner_1 = Layout::new(
-            layout::Direction::Horizontal,
-            self.options.iter().map(|_| Constraint::Fill(1)),
-        )
-        .flex(layout::Flex::SpaceBetween)
-        .split(inner_0[1]);
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
 
-        Block::new()
-            .title(self.title)
-            .borders(Borders::ALL)
-            .border_style(match self.focused {
-                true => ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
-                false => ratatui::style::Style::default(),
-            })
-            .render(area, buf);
+            render_compact_label(area, buf, label, self.focused, &self.theme);
+        } else {
+            self.theme
+                .block(
+                    shown_title(self.show_title, &self.title).to_string(),
+                    self.focused,
+                )
+                .render(area, buf);
+        }
 
         self.options.iter().enumerate().for_each(|(i, (l, v))| {
-            Paragraph::new(Line::from(vec![Span::styled(l.clone(), {
-                let st = Style::new();
-                match self.value == v.to_string() {
-                    true => st.bold().fg(Color::Yellow),
-                    false => st,
-                }
-            })]))
+            Paragraph::new(Line::from(vec![Span::styled(
+                self.option_text(l, self.value == *v),
+                match self.value == *v {
+                    true => self.theme.selection,
+                    false => Style::new(),
+                },
+            )]))
             .alignment(Alignment::Center)
             .render(inner_1[i], buf);
         });
@@ -1,3 +1,4 @@
+use crossterm::event::{MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Position, Rect},
@@ -51,13 +52,36 @@ impl Scroll {
         self.offset = offset;
         self.clone()
     }
+
+    /// Moves the view by `rows`, keeping the page within the content.
+    pub fn scroll_by(&mut self, rows: i32) {
+        let max = self
+            .max_size
+            .bottom()
+            .saturating_sub(self.page_size.height)
+            .max(self.max_size.y);
+
+        self.offset.y =
+            (self.offset.y as i32 + rows).clamp(self.max_size.y as i32, max as i32) as u16;
+    }
+
+    /// Scrolls on wheel events, returning whether `m` was handled.
+    pub fn handle_mouse(&mut self, m: MouseEvent) -> bool {
+        match m.kind {
+            MouseEventKind::ScrollDown => self.scroll_by(1),
+            MouseEventKind::ScrollUp => self.scroll_by(-1),
+            _ => return false,
+        }
+
+        true
+    }
 }
 
 impl Widget for Scroll {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let selection = Rect {
-            x: self.offset.x.max(0),
-            y: self.offset.y.max(0),
+            x: self.offset.x,
+            y: self.offset.y,
             width: self.page_size.width,
             height: self.page_size.height,
         };
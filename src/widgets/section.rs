@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::Widget,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::{Form, FormItem, Theme};
+
+/// A header grouping the items after it, up to the next `Section`.
+///
+/// Enter, Space or a click collapses it, taking its items out of the tab
+/// order and the layout. They still count towards validation.
+#[derive(Clone)]
+pub struct Section {
+    pub name: String,
+    pub title: String,
+    pub collapsed: bool,
+    pub focused: bool,
+    /// Whether a collapsed header shows how many of its items are invalid.
+    pub show_invalid_count: bool,
+    initial: bool,
+    theme: Theme,
+}
+
+impl Section {
+    pub fn new(name: &str, title: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            collapsed: false,
+            focused: false,
+            show_invalid_count: false,
+            initial: false,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Starts out collapsed.
+    pub fn collapsed(self) -> Self {
+        Self {
+            collapsed: true,
+            initial: true,
+            ..self
+        }
+    }
+
+    pub fn invalid_count(self) -> Self {
+        Self {
+            show_invalid_count: true,
+            ..self
+        }
+    }
+}
+
+impl FormItem for Section {
+    fn ren(&self, a: Rect, b: &mut Buffer) {
+        self.clone().render(a, b);
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn input(&mut self, k: KeyEvent) {
+        if matches!(k.code, KeyCode::Enter | KeyCode::Char(' ')) {
+            self.collapsed = !self.collapsed;
+        }
+    }
+
+    fn mouse(&mut self, m: MouseEvent, _: Rect) {
+        if m.kind == MouseEventKind::Down(MouseButton::Left) {
+            self.collapsed = !self.collapsed;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.collapsed = self.initial;
+    }
+
+    fn height(&self) -> u16 {
+        1
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+}
+
+impl Widget for Section {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let marker = match self.collapsed {
+            true => self.theme.glyphs().collapsed,
+            false => self.theme.glyphs().expanded,
+        };
+
+        let style = match self.focused {
+            true => self.theme.selection,
+            false => self.theme.title.bold(),
+        };
+
+        Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(self.title, style),
+        ])
+        .render(area, buf);
+    }
+}
+
+/// Writes how many items are invalid after a collapsed header's title.
+pub(super) fn render_invalid_count(
+    area: Rect,
+    buf: &mut Buffer,
+    title: &str,
+    count: usize,
+    style: Style,
+) {
+    let x = area.x + 2 + title.width() as u16 + 1;
+    if count == 0 || x >= area.right() {
+        return;
+    }
+
+    buf.set_stringn(
+        x,
+        area.y,
+        format!("({count} invalid)"),
+        (area.right() - x) as usize,
+        style,
+    );
+}
+
+impl Form {
+    /// For each item, the index of the [`Section`] it falls under.
+    pub(super) fn sections(&self) -> Vec<Option<usize>> {
+        let mut current = None;
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                if (**item.read().unwrap()).as_any().is::<Section>() {
+                    current = Some(i);
+                    return None;
+                }
+
+                current
+            })
+            .collect()
+    }
+
+    /// How many distinct items under the section at `i` are invalid.
+    pub(super) fn invalid_in_section(&self, i: usize, errors: &[(String, String)]) -> usize {
+        let sections = self.sections();
+        let names = self
+            .items
+            .iter()
+            .zip(sections)
+            .filter(|(_, s)| *s == Some(i))
+            .map(|(item, _)| item.read().unwrap().name())
+            .collect::<HashSet<_>>();
+
+        errors
+            .iter()
+            .map(|(n, _)| n)
+            .filter(|n| names.contains(*n))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
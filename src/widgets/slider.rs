@@ -1,22 +1,24 @@
 use std::ops::{Add, Sub};
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget},
 };
+use unicode_width::UnicodeWidthStr;
 
-use super::FormItem;
+use super::{
+    is_horizontal_arrow, render_compact_label, shown_title, value_area, FormItem, FormValue, Theme,
+};
 
 pub fn map_range(value: i32, in_range: (i32, i32), out_range: (i32, i32)) -> i32 {
     let (in_min, in_max) = in_range;
     let (out_min, out_max) = out_range;
     let value = value.max(in_min).min(in_max);
-    let mapped_value = (value - in_min) * (out_max - out_min) / (in_max - in_min) + out_min;
-    mapped_value
+    (value - in_min) * (out_max - out_min) / (in_max - in_min) + out_min
 }
 
 #[derive(Clone)]
@@ -26,7 +28,11 @@ pub struct Slider {
     pub value: i32,
     pub range: (i32, i32),
     pub focused: bool,
+    show_title: bool,
+    compact: bool,
+    theme: Theme,
     pub units: Option<String>,
+    initial: i32,
 }
 
 impl Slider {
@@ -42,9 +48,42 @@ impl Slider {
             range,
             value,
             focused: false,
+            show_title: true,
+            compact: false,
+            theme: Theme::default(),
             title: title.to_string(),
             units: units.map(|s| s.to_string()),
+            initial: value,
+        }
+    }
+
+    /// Draws on one row as `Title: value`.
+    pub fn compact(self) -> Self {
+        Self {
+            compact: true,
+            ..self
+        }
+    }
+}
+
+impl Slider {
+    /// Where the bar goes, leaving room for the value after it when compact.
+    fn track(&self, area: Rect) -> Rect {
+        if !self.compact {
+            return value_area(area, "", false);
         }
+
+        let label = shown_title(self.show_title, &self.title);
+
+        let area = value_area(area, label, true);
+        Rect {
+            width: area.width.saturating_sub(self.reading().width() as u16 + 1),
+            ..area
+        }
+    }
+
+    fn reading(&self) -> String {
+        format!("{}{}", self.value, self.units.clone().unwrap_or_default())
     }
 }
 
@@ -65,12 +104,59 @@ impl FormItem for Slider {
         self.clone().render(a, b);
     }
 
-    fn value(&self) -> Option<String> {
-        Some(self.value.to_string())
+    fn label(&self) -> String {
+        self.title.clone()
+    }
+
+    fn set_show_title(&mut self, show: bool) {
+        self.show_title = show;
+    }
+
+    fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = *theme;
+    }
+
+    fn height(&self) -> u16 {
+        match self.compact {
+            true => 1,
+            false => 3,
+        }
+    }
+
+    fn should_prevent_nav(&self, k: KeyEvent) -> bool {
+        is_horizontal_arrow(&k)
+    }
+
+    fn value(&self) -> FormValue {
+        FormValue::Int(self.value as i64)
+    }
+
+    /// Numbers (and numeric text) are clamped to the range.
+    fn set_value(&mut self, v: FormValue) -> bool {
+        let n = match v {
+            FormValue::Int(i) => i,
+            FormValue::Float(f) => f.round() as i64,
+            FormValue::Text(s) => match s.trim().parse() {
+                Ok(i) => i,
+                Err(_) => return false,
+            },
+            _ => return false,
+        };
+
+        self.value = n.clamp(self.range.0 as i64, self.range.1 as i64) as i32;
+        true
+    }
+
+    fn reset(&mut self) {
+        self.value = self.initial;
     }
 
-    fn input(&mut self, k: KeyCode) {
-        match k {
+    fn input(&mut self, k: KeyEvent) {
+        match k.code {
             KeyCode::Left => self.value = self.value.sub(1).max(self.range.0),
             KeyCode::Right => self.value = self.value.add(1).min(self.range.1),
             KeyCode::Home => self.value = self.range.0,
@@ -78,6 +164,17 @@ impl FormItem for Slider {
             _ => {}
         }
     }
+
+    fn mouse(&mut self, m: MouseEvent, a: Rect) {
+        if let MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left) =
+            m.kind
+        {
+            let track = self.track(a);
+            let x = m.column as i32 - track.x as i32;
+            let track = track.width.max(1) as i32;
+            self.value = map_range(x.clamp(0, track), (0, track), self.range);
+        }
+    }
 }
 
 impl Widget for Slider {
@@ -85,36 +182,59 @@ impl Widget for Slider {
     where
         Self: Sized,
     {
-        let char_area = area.right() - area.left();
+        let track = self.track(area);
+        let char_area = track.width;
         let filled_proportion = map_range(self.value, self.range, (0, char_area as i32));
 
+        // Without colour, the bar needs glyphs to show where it's filled to.
+        let (filled, empty) = match self.theme.monochrome {
+            true => {
+                let glyphs = self.theme.glyphs();
+                (glyphs.slider_fill, glyphs.slider_track)
+            }
+            false => (" ", " "),
+        };
+
         let bar = vec![
             Span::styled(
-                " ".repeat(filled_proportion as usize),
-                Style::new().bg(Color::Yellow),
+                filled.repeat(filled_proportion as usize),
+                self.theme.slider_fill,
             ),
             Span::styled(
-                " ".repeat(char_area as usize - filled_proportion as usize),
-                Style::new().bg(Color::White),
+                empty.repeat(char_area as usize - filled_proportion as usize),
+                self.theme.slider_track,
             ),
         ];
 
+        if self.compact {
+            let label = shown_title(self.show_title, &self.title);
+
+            render_compact_label(area, buf, label, self.focused, &self.theme);
+            Line::from(bar).render(track, buf);
+            buf.set_stringn(
+                track.right() + 1,
+                track.y,
+                self.reading(),
+                area.right().saturating_sub(track.right() + 1) as usize,
+                Style::new(),
+            );
+            return;
+        }
+
         Paragraph::new(Line::from(bar))
             .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .title(format!(
+            .block(self.theme.block(
+                match self.show_title {
+                    true => format!(
                         "{}: {}{}",
                         self.title,
                         self.value,
                         self.units.unwrap_or_default()
-                    ))
-                    .borders(Borders::ALL)
-                    .border_style(match self.focused {
-                        true => ratatui::style::Style::default().fg(ratatui::style::Color::Yellow),
-                        false => ratatui::style::Style::default(),
-                    }),
-            )
+                    ),
+                    false => format!("{}{}", self.value, self.units.unwrap_or_default()),
+                },
+                self.focused,
+            ))
             .render(area, buf);
     }
 }
@@ -0,0 +1,420 @@
+use std::fmt::Display;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::Theme;
+
+/// Whether `k` is a shortcut (Ctrl / Alt held) rather than plain typing.
+///
+/// Ctrl and Alt together on a character count as typing, since that's how
+/// Windows reports AltGr (`@`, `{`, `€` on many European layouts).
+pub fn has_command_modifier(k: &KeyEvent) -> bool {
+    let chord = KeyModifiers::CONTROL | KeyModifiers::ALT;
+    if matches!(k.code, KeyCode::Char(_)) && k.modifiers.contains(chord) {
+        return false;
+    }
+
+    k.modifiers.intersects(chord)
+}
+
+/// Left / Right without Alt, which editing widgets keep for themselves
+/// rather than letting the form move between columns.
+pub fn is_horizontal_arrow(k: &KeyEvent) -> bool {
+    matches!(k.code, KeyCode::Left | KeyCode::Right) && !k.modifiers.contains(KeyModifiers::ALT)
+}
+
+/// Highlights the grapheme at `c` (a grapheme index, not a byte index).
+pub fn add_cursor<'a>(s: String, c: usize) -> Line<'a> {
+    cursor_line(s, c, None, Theme::default().cursor)
+}
+
+/// Like [`add_cursor`], but draws `cc` in place of the grapheme under the cursor.
+pub fn add_reveal_cursor<'a>(s: String, c: usize, cc: char) -> Line<'a> {
+    cursor_line(s, c, Some(&cc.to_string()), Theme::default().cursor)
+}
+
+/// Draws the grapheme at `c` (or `cc` in its place) in `style`.
+fn cursor_line<'a>(s: String, c: usize, cc: Option<&str>, style: Style) -> Line<'a> {
+    let graphemes = s.graphemes(true).collect::<Vec<_>>();
+    let c = c.min(graphemes.len());
+    let under = cc.unwrap_or(graphemes.get(c).copied().unwrap_or(" "));
+
+    Line::from(vec![
+        Span::raw(graphemes[..c].concat()),
+        Span::styled(under.to_string(), style),
+        Span::raw(graphemes.get(c + 1..).unwrap_or_default().concat()),
+    ])
+}
+
+/// An editable string with a cursor.
+///
+/// The cursor is counted in grapheme clusters, so "é", emoji and CJK text
+/// move and delete as a single unit, and scrolling is measured in display
+/// columns.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct TextBuffer {
+    value: String,
+    cursor: usize,
+}
+
+impl TextBuffer {
+    /// Creates a buffer with the cursor placed after the last grapheme.
+    pub fn new(value: &str) -> Self {
+        let mut buffer = Self::default();
+        buffer.set(value);
+        buffer
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Number of graphemes in the buffer.
+    pub fn len(&self) -> usize {
+        self.value.graphemes(true).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Number of terminal columns the buffer occupies.
+    pub fn width(&self) -> usize {
+        self.value.width()
+    }
+
+    /// Replaces the contents and moves the cursor to the end.
+    pub fn set(&mut self, value: &str) {
+        self.value = value.to_string();
+        self.cursor = self.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    pub fn set_cursor(&mut self, c: usize) {
+        self.cursor = c.min(self.len());
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let at = self.byte_offset(self.cursor);
+        self.value.insert(at, c);
+
+        // Combining marks merge into the previous grapheme, so recount
+        // rather than assuming the cursor moved by one.
+        self.cursor = self.value[..at + c.len_utf8()].graphemes(true).count();
+    }
+
+    pub fn backspace(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+
+        let start = self.byte_offset(self.cursor - 1);
+        let end = self.byte_offset(self.cursor);
+        self.value.replace_range(start..end, "");
+        self.cursor -= 1;
+        true
+    }
+
+    pub fn delete(&mut self) -> bool {
+        if self.cursor >= self.len() {
+            return false;
+        }
+
+        let start = self.byte_offset(self.cursor);
+        let end = self.byte_offset(self.cursor + 1);
+        self.value.replace_range(start..end, "");
+        true
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.len());
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.len();
+    }
+
+    /// Applies the standard editing keys, returning whether `k` was handled.
+    ///
+    /// Ctrl+A / Ctrl+E jump to the start / end; other Ctrl and Alt chords are
+    /// left for the caller instead of being typed.
+    pub fn input(&mut self, k: KeyEvent) -> bool {
+        let command = has_command_modifier(&k);
+        match k.code {
+            KeyCode::Char('a') if command => self.home(),
+            KeyCode::Char('e') if command => self.end(),
+            KeyCode::Char(_) if command => return false,
+            KeyCode::Backspace => {
+                self.backspace();
+            }
+            KeyCode::Delete => {
+                self.delete();
+            }
+            KeyCode::Left => self.left(),
+            KeyCode::Right => self.right(),
+            KeyCode::Home => self.home(),
+            KeyCode::End => self.end(),
+            KeyCode::Char(c) => self.insert(c),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Renders the buffer into a line `width` columns wide. Given a `cursor`
+    /// style (while focused), draws the cursor in it and scrolls to keep it
+    /// visible.
+    pub fn line<'a>(&self, width: usize, cursor: Option<Style>) -> Line<'a> {
+        let Some(style) = cursor else {
+            return Line::raw(self.value.clone());
+        };
+
+        let graphemes = self.value.graphemes(true).collect::<Vec<_>>();
+        let widths = graphemes.iter().map(|g| g.width()).collect::<Vec<_>>();
+        let offset = scroll_offset(&widths, self.cursor, width);
+
+        cursor_line(
+            graphemes[offset..].concat(),
+            self.cursor - offset,
+            None,
+            style,
+        )
+    }
+
+    /// Renders the buffer with every grapheme replaced by `mask`, revealing
+    /// only the grapheme under the cursor while focused.
+    pub fn masked_line<'a>(&self, width: usize, cursor: Option<Style>, mask: char) -> Line<'a> {
+        let len = self.len();
+        let Some(style) = cursor else {
+            return Line::raw(mask.to_string().repeat(len));
+        };
+
+        let offset = scroll_offset(&vec![1; len], self.cursor, width);
+        let revealed = self.value.graphemes(true).nth(self.cursor).unwrap_or(" ");
+
+        cursor_line(
+            mask.to_string().repeat(len - offset),
+            self.cursor - offset,
+            Some(revealed),
+            style,
+        )
+    }
+
+    /// Moves the cursor to the grapheme drawn at `column` of a
+    /// [`line`](Self::line) `width` columns wide.
+    pub fn place_cursor(&mut self, column: usize, width: usize) {
+        let widths = self
+            .value
+            .graphemes(true)
+            .map(|g| g.width())
+            .collect::<Vec<_>>();
+
+        let offset = scroll_offset(&widths, self.cursor, width);
+        self.cursor = grapheme_at(&widths, offset, column);
+    }
+
+    /// [`place_cursor`](Self::place_cursor) for a [`masked_line`](Self::masked_line).
+    pub fn place_masked_cursor(&mut self, column: usize, width: usize) {
+        let widths = vec![1; self.len()];
+        let offset = scroll_offset(&widths, self.cursor, width);
+        self.cursor = grapheme_at(&widths, offset, column);
+    }
+
+    fn byte_offset(&self, c: usize) -> usize {
+        self.value
+            .grapheme_indices(true)
+            .nth(c)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+}
+
+impl Display for TextBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl From<&str> for TextBuffer {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for TextBuffer {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl AsRef<str> for TextBuffer {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl PartialEq<str> for TextBuffer {
+    fn eq(&self, other: &str) -> bool {
+        self.value == other
+    }
+}
+
+impl PartialEq<&str> for TextBuffer {
+    fn eq(&self, other: &&str) -> bool {
+        self.value == *other
+    }
+}
+
+/// First grapheme to draw so the cursor cell still fits within `width`.
+fn scroll_offset(widths: &[usize], cursor: usize, width: usize) -> usize {
+    let mut used = widths.get(cursor).copied().unwrap_or(1).max(1);
+    let mut offset = cursor;
+
+    while offset > 0 && used + widths[offset - 1] <= width {
+        used += widths[offset - 1];
+        offset -= 1;
+    }
+
+    offset
+}
+
+/// Grapheme drawn at `column` when drawing starts from grapheme `offset`.
+fn grapheme_at(widths: &[usize], offset: usize, column: usize) -> usize {
+    let mut x = 0;
+    for (i, w) in widths.iter().enumerate().skip(offset) {
+        if column < x + w {
+            return i;
+        }
+
+        x += w;
+    }
+
+    widths.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::*;
+
+    fn typed(s: &str) -> TextBuffer {
+        let mut buffer = TextBuffer::default();
+        s.chars().for_each(|c| buffer.insert(c));
+        buffer
+    }
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn combining_marks_are_one_grapheme() {
+        let mut buffer = typed("cafe\u{301}");
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(buffer.cursor(), 4);
+        assert_eq!(buffer.width(), 4);
+
+        buffer.left();
+        assert_eq!(buffer.cursor(), 3);
+        buffer.right();
+        assert!(buffer.backspace());
+        assert_eq!(buffer, "caf");
+    }
+
+    #[test]
+    fn zwj_emoji_is_one_grapheme() {
+        let family = "\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        let mut buffer = TextBuffer::new(&format!("a{family}b"));
+        assert_eq!(buffer.len(), 3);
+
+        buffer.left();
+        buffer.left();
+        assert_eq!(buffer.cursor(), 1);
+        assert!(buffer.delete());
+        assert_eq!(buffer, "ab");
+
+        buffer.set(family);
+        assert!(buffer.backspace());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn cjk_is_two_columns_wide() {
+        let mut buffer = TextBuffer::new("日本語");
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.width(), 6);
+
+        buffer.home();
+        buffer.place_cursor(3, 10);
+        assert_eq!(buffer.cursor(), 1);
+        buffer.place_cursor(4, 10);
+        assert_eq!(buffer.cursor(), 2);
+
+        // The cursor cell past the end needs one column, so only the last
+        // character fits beside it.
+        buffer.end();
+        let line = buffer.line(3, Some(Style::new()));
+        assert_eq!(text(&line), "語 ");
+    }
+
+    #[test]
+    fn editing_keys() {
+        let mut buffer = TextBuffer::new("héllo");
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        assert!(buffer.input(ctrl('a')));
+        assert_eq!(buffer.cursor(), 0);
+        assert!(buffer.input(KeyEvent::from(KeyCode::Delete)));
+        assert!(buffer.input(ctrl('e')));
+        assert_eq!(buffer.cursor(), 4);
+        assert!(!buffer.input(ctrl('x')));
+        assert!(buffer.input(KeyEvent::from(KeyCode::Char('!'))));
+        assert_eq!(buffer, "éllo!");
+    }
+
+    #[test]
+    fn altgr_types() {
+        let mut buffer = TextBuffer::default();
+        let altgr = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        for c in ['@', '{', '€'] {
+            assert!(buffer.input(KeyEvent::new(KeyCode::Char(c), altgr)));
+        }
+
+        assert_eq!(buffer, "@{€");
+        assert!(!buffer.input(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::ALT)));
+    }
+
+    #[test]
+    fn masked_line_reveals_the_cursor() {
+        let mut buffer = TextBuffer::new("日本");
+        buffer.home();
+        assert_eq!(
+            text(&buffer.masked_line(10, Some(Style::new()), '*')),
+            "日*"
+        );
+        assert_eq!(text(&buffer.masked_line(10, None, '*')), "**");
+    }
+}
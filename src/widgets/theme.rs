@@ -0,0 +1,190 @@
+use std::env;
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, BorderType, Borders},
+};
+
+use super::{glyphs, Form, Glyphs};
+
+/// The colours and borders the built-in widgets draw with. Set one on a
+/// [`Form`] with [`Form::set_theme`], or on a [`Popup`](super::Popup).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Border of the focused item.
+    pub focused: Style,
+    pub unfocused: Style,
+    /// Border and message of an item that failed validation.
+    pub invalid: Style,
+    /// Laid over items that are disabled.
+    pub disabled: Style,
+    pub border_type: BorderType,
+    /// Border of the focused item, which may differ from the rest.
+    pub focused_border_type: BorderType,
+    pub cursor: Style,
+    /// The chosen option, the selected row, and focused labels.
+    pub selection: Style,
+    /// List values that aren't selected.
+    pub item: Style,
+    /// Secondary text, like counts and spacers.
+    pub muted: Style,
+    pub slider_fill: Style,
+    pub slider_track: Style,
+    pub title: Style,
+    /// Marks state with glyphs as well as style: a `>` before the focused
+    /// item's title, brackets around choices and a drawn slider bar.
+    pub monochrome: bool,
+    /// Draws with these instead of the global [`glyphs`], when set.
+    pub glyphs: Option<Glyphs>,
+}
+
+/// `dark`, or `monochrome` when `NO_COLOR` is set.
+impl Default for Theme {
+    fn default() -> Self {
+        match no_color() {
+            true => Self::monochrome(),
+            false => Self::dark(),
+        }
+    }
+}
+
+/// Whether the `NO_COLOR` environment variable asks for no colour.
+pub fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+impl Theme {
+    /// Yellow on a dark terminal. The default.
+    pub fn dark() -> Self {
+        Self {
+            focused: Style::new().fg(Color::Yellow),
+            unfocused: Style::new(),
+            invalid: Style::new().fg(Color::Red),
+            disabled: Style::new().add_modifier(Modifier::DIM),
+            border_type: BorderType::Plain,
+            focused_border_type: BorderType::Plain,
+            cursor: Style::new().bg(Color::Yellow),
+            selection: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            item: Style::new().fg(Color::Black).bg(Color::White),
+            muted: Style::new().fg(Color::Gray),
+            slider_fill: Style::new().bg(Color::Yellow),
+            slider_track: Style::new().bg(Color::White),
+            title: Style::new(),
+            monochrome: false,
+            glyphs: None,
+        }
+    }
+
+    /// Blue on a light terminal.
+    pub fn light() -> Self {
+        Self {
+            focused: Style::new().fg(Color::Blue),
+            unfocused: Style::new().fg(Color::DarkGray),
+            invalid: Style::new().fg(Color::Red),
+            disabled: Style::new().add_modifier(Modifier::DIM),
+            border_type: BorderType::Rounded,
+            focused_border_type: BorderType::Rounded,
+            cursor: Style::new().fg(Color::White).bg(Color::Blue),
+            selection: Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD),
+            item: Style::new().fg(Color::White).bg(Color::DarkGray),
+            muted: Style::new().fg(Color::DarkGray),
+            slider_fill: Style::new().bg(Color::Blue),
+            slider_track: Style::new().bg(Color::Gray),
+            title: Style::new().fg(Color::Black),
+            monochrome: false,
+            glyphs: None,
+        }
+    }
+
+    /// Bright, bold and thick, for low-vision use.
+    pub fn high_contrast() -> Self {
+        Self {
+            focused: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            unfocused: Style::new().fg(Color::White),
+            invalid: Style::new()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            disabled: Style::new().fg(Color::DarkGray),
+            border_type: BorderType::Plain,
+            focused_border_type: BorderType::Thick,
+            cursor: Style::new().add_modifier(Modifier::REVERSED),
+            selection: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            item: Style::new().fg(Color::Black).bg(Color::White),
+            muted: Style::new().fg(Color::White),
+            slider_fill: Style::new().bg(Color::LightYellow),
+            slider_track: Style::new().bg(Color::DarkGray),
+            title: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            monochrome: false,
+            glyphs: None,
+        }
+    }
+
+    /// No colour at all: reverse video, bold, thick borders and glyphs.
+    pub fn monochrome() -> Self {
+        Self {
+            focused: Style::new().add_modifier(Modifier::BOLD),
+            unfocused: Style::new(),
+            invalid: Style::new().add_modifier(Modifier::BOLD),
+            disabled: Style::new().add_modifier(Modifier::DIM),
+            border_type: BorderType::Plain,
+            focused_border_type: BorderType::Thick,
+            cursor: Style::new().add_modifier(Modifier::REVERSED),
+            selection: Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            item: Style::new(),
+            muted: Style::new().add_modifier(Modifier::DIM),
+            slider_fill: Style::new(),
+            slider_track: Style::new().add_modifier(Modifier::DIM),
+            title: Style::new(),
+            monochrome: true,
+            glyphs: None,
+        }
+    }
+
+    pub fn border(&self, focused: bool) -> Style {
+        match focused {
+            true => self.focused,
+            false => self.unfocused,
+        }
+    }
+
+    /// [`glyphs`](Self::glyphs) if set, otherwise the global ones.
+    pub fn glyphs(&self) -> &Glyphs {
+        self.glyphs.as_ref().unwrap_or_else(|| glyphs())
+    }
+
+    /// A bordered block in this theme, as the built-in widgets draw.
+    pub fn block<'a>(&self, title: String, focused: bool) -> Block<'a> {
+        let title = match self.monochrome && focused {
+            true => format!("> {title}"),
+            false => title,
+        };
+
+        Block::new()
+            .title(title)
+            .title_style(self.title)
+            .borders(Borders::ALL)
+            .border_set(self.glyphs().border_set(match focused {
+                true => self.focused_border_type,
+                false => self.border_type,
+            }))
+            .border_style(self.border(focused))
+    }
+}
+
+impl Form {
+    /// Restyles the form and every item in it, including ones added later.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        for item in &self.items {
+            item.write().unwrap().set_theme(&theme);
+        }
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+}
@@ -0,0 +1,380 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::Display,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::BorderType,
+};
+use toml::{Table, Value};
+
+use super::Theme;
+
+/// Why a theme file couldn't be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeError {
+    /// The file, when loaded from one.
+    pub path: Option<PathBuf>,
+    pub message: String,
+}
+
+impl ThemeError {
+    fn new(message: impl Display) -> Self {
+        Self {
+            path: None,
+            message: message.to_string(),
+        }
+    }
+
+    fn at(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_path_buf());
+        self
+    }
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}: {}", path.display(), self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl Theme {
+    /// Reads a theme from TOML, starting from the palette named by `base`
+    /// (`dark`, `light`, `high-contrast` or `monochrome`; [`Theme::default`]
+    /// if left out):
+    ///
+    /// ```toml
+    /// base = "light"
+    /// border_type = "rounded"
+    ///
+    /// [focused]
+    /// fg = "#ff8800"        # or a name like "yellow", or an ANSI index like 214
+    /// modifiers = ["bold"]
+    /// border_type = "double"
+    /// ```
+    ///
+    /// The styles are `focused`, `unfocused`, `invalid`, `disabled`, `cursor`,
+    /// `selection`, `item`, `muted`, `slider_fill`, `slider_track` and
+    /// `title`. Each one given replaces the palette's entirely. Only
+    /// `focused` and `unfocused` take a `border_type`; the top-level one sets
+    /// both. A top-level `monochrome = true` turns on [`Theme::monochrome`]'s
+    /// glyphs.
+    pub fn from_toml(s: &str) -> Result<Theme, ThemeError> {
+        let table = s.parse::<Table>().map_err(ThemeError::new)?;
+        let mut theme = match table.get("base") {
+            None => Theme::default(),
+            Some(base) => match as_str(base, "base")? {
+                "dark" => Theme::dark(),
+                "light" => Theme::light(),
+                "high-contrast" | "high_contrast" => Theme::high_contrast(),
+                "monochrome" => Theme::monochrome(),
+                other => return Err(ThemeError::new(format!("unknown base theme `{other}`"))),
+            },
+        };
+
+        if let Some(border) = table.get("border_type") {
+            theme.border_type = border_type(border, "border_type")?;
+            theme.focused_border_type = theme.border_type;
+        }
+
+        if let Some(monochrome) = table.get("monochrome") {
+            theme.monochrome = monochrome
+                .as_bool()
+                .ok_or_else(|| ThemeError::new("`monochrome` should be true or false"))?;
+        }
+
+        for (key, value) in &table {
+            let style = match key.as_str() {
+                "base" | "border_type" | "monochrome" => continue,
+                "focused" => &mut theme.focused,
+                "unfocused" => &mut theme.unfocused,
+                "invalid" => &mut theme.invalid,
+                "disabled" => &mut theme.disabled,
+                "cursor" => &mut theme.cursor,
+                "selection" => &mut theme.selection,
+                "item" => &mut theme.item,
+                "muted" => &mut theme.muted,
+                "slider_fill" => &mut theme.slider_fill,
+                "slider_track" => &mut theme.slider_track,
+                "title" => &mut theme.title,
+                _ => return Err(ThemeError::new(format!("unknown key `{key}`"))),
+            };
+
+            let Value::Table(fields) = value else {
+                return Err(ThemeError::new(format!("`{key}` should be a table")));
+            };
+
+            *style = parse_style(key, fields)?;
+            if let Some(border) = fields.get("border_type") {
+                let border = border_type(border, &format!("{key}.border_type"))?;
+                match key.as_str() {
+                    "focused" => theme.focused_border_type = border,
+                    "unfocused" => theme.border_type = border,
+                    _ => return Err(ThemeError::new(format!("`{key}` can't have a border_type"))),
+                }
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Reads a theme from a TOML file. See [`from_toml`](Self::from_toml).
+    pub fn load(path: impl AsRef<Path>) -> Result<Theme, ThemeError> {
+        let path = path.as_ref();
+        fs::read_to_string(path)
+            .map_err(ThemeError::new)
+            .and_then(|s| Theme::from_toml(&s))
+            .map_err(|e| e.at(path))
+    }
+}
+
+fn as_str<'a>(value: &'a Value, key: &str) -> Result<&'a str, ThemeError> {
+    value
+        .as_str()
+        .ok_or_else(|| ThemeError::new(format!("`{key}` should be a string")))
+}
+
+fn parse_style(key: &str, fields: &Table) -> Result<Style, ThemeError> {
+    let mut style = Style::new();
+    for (field, value) in fields {
+        let name = format!("{key}.{field}");
+        match field.as_str() {
+            "fg" => style = style.fg(color(value, &name)?),
+            "bg" => style = style.bg(color(value, &name)?),
+            "modifiers" => style = style.add_modifier(modifiers(value, &name)?),
+            "border_type" => {}
+            _ => return Err(ThemeError::new(format!("unknown key `{name}`"))),
+        }
+    }
+
+    Ok(style)
+}
+
+/// A name, `#rrggbb`, or ANSI index (as a number or a string).
+fn color(value: &Value, key: &str) -> Result<Color, ThemeError> {
+    match value {
+        Value::Integer(i) => u8::try_from(*i)
+            .map(Color::Indexed)
+            .map_err(|_| ThemeError::new(format!("`{key}`: {i} isn't an ANSI index (0-255)"))),
+        _ => as_str(value, key)?
+            .parse()
+            .map_err(|_| ThemeError::new(format!("`{key}`: unknown color `{value}`"))),
+    }
+}
+
+fn modifiers(value: &Value, key: &str) -> Result<Modifier, ThemeError> {
+    let Value::Array(names) = value else {
+        return Err(ThemeError::new(format!("`{key}` should be a list")));
+    };
+
+    names.iter().try_fold(Modifier::empty(), |all, name| {
+        let modifier = match as_str(name, key)? {
+            "bold" => Modifier::BOLD,
+            "dim" => Modifier::DIM,
+            "italic" => Modifier::ITALIC,
+            "underlined" => Modifier::UNDERLINED,
+            "slow_blink" => Modifier::SLOW_BLINK,
+            "rapid_blink" => Modifier::RAPID_BLINK,
+            "reversed" => Modifier::REVERSED,
+            "hidden" => Modifier::HIDDEN,
+            "crossed_out" => Modifier::CROSSED_OUT,
+            other => {
+                return Err(ThemeError::new(format!(
+                    "`{key}`: unknown modifier `{other}`"
+                )))
+            }
+        };
+
+        Ok(all | modifier)
+    })
+}
+
+fn border_type(value: &Value, key: &str) -> Result<BorderType, ThemeError> {
+    Ok(match as_str(value, key)? {
+        "plain" => BorderType::Plain,
+        "rounded" => BorderType::Rounded,
+        "double" => BorderType::Double,
+        "thick" => BorderType::Thick,
+        "quadrant_inside" => BorderType::QuadrantInside,
+        "quadrant_outside" => BorderType::QuadrantOutside,
+        other => {
+            return Err(ThemeError::new(format!(
+                "`{key}`: unknown border type `{other}`"
+            )))
+        }
+    })
+}
+
+/// Reloads a theme file when it changes, checking its modification time and
+/// contents at most once per interval (half a second unless set). Contents
+/// count too, since two writes can land within one timestamp tick.
+///
+/// Call [`poll`](Self::poll) from the event loop:
+///
+/// ```ignore
+/// match watcher.poll() {
+///     Some(Ok(theme)) => form.set_theme(theme),
+///     Some(Err(e)) => status = e.to_string(),
+///     None => {}
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThemeWatcher {
+    pub path: PathBuf,
+    pub interval: Duration,
+    checked: Option<Instant>,
+    /// The file as last read; `None` if it couldn't be.
+    seen: Option<(SystemTime, u64)>,
+}
+
+impl ThemeWatcher {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            interval: Duration::from_millis(500),
+            checked: None,
+            seen: None,
+        }
+    }
+
+    pub fn interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Loads the file now, whether or not it changed.
+    pub fn load(&mut self) -> Result<Theme, ThemeError> {
+        self.checked = Some(Instant::now());
+        self.seen = self.stamp();
+        Theme::load(&self.path)
+    }
+
+    /// The reloaded theme, or why it couldn't be used, if the file changed
+    /// (or went missing) since it was last read. The first poll always
+    /// loads it.
+    pub fn poll(&mut self) -> Option<Result<Theme, ThemeError>> {
+        if self.checked.is_some_and(|at| at.elapsed() < self.interval) {
+            return None;
+        }
+
+        let first = self.checked.is_none();
+        self.checked = Some(Instant::now());
+        let seen = self.stamp();
+        if !first && seen == self.seen {
+            return None;
+        }
+
+        self.seen = seen;
+        Some(Theme::load(&self.path))
+    }
+
+    /// The file's modification time and a hash of its contents.
+    fn stamp(&self) -> Option<(SystemTime, u64)> {
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok()?;
+        let contents = fs::read(&self.path).ok()?;
+        let mut hasher = DefaultHasher::new();
+        contents.hash(&mut hasher);
+        Some((modified, hasher.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+
+    fn error(s: &str) -> String {
+        Theme::from_toml(s).unwrap_err().message
+    }
+
+    #[test]
+    fn reads_colours_modifiers_and_borders() {
+        let theme = Theme::from_toml(
+            r##"
+            base = "light"
+            border_type = "rounded"
+
+            [focused]
+            fg = "#ff8800"
+            bg = "blue"
+            modifiers = ["bold", "underlined"]
+            border_type = "double"
+
+            [muted]
+            fg = 244
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            theme.focused,
+            Style::new()
+                .fg(Color::Rgb(0xff, 0x88, 0x00))
+                .bg(Color::Blue)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        );
+        assert_eq!(theme.muted, Style::new().fg(Color::Indexed(244)));
+        assert_eq!(theme.border_type, BorderType::Rounded);
+        assert_eq!(theme.focused_border_type, BorderType::Double);
+        assert_eq!(theme.invalid, Theme::light().invalid);
+    }
+
+    #[test]
+    fn rejects_unknown_and_misplaced_keys() {
+        assert_eq!(error("colour = 1"), "unknown key `colour`");
+        assert_eq!(
+            error("[title]\nborder_type = \"double\""),
+            "`title` can't have a border_type"
+        );
+        assert_eq!(
+            error("[title]\nunderline = true"),
+            "unknown key `title.underline`"
+        );
+        assert_eq!(
+            error("[title]\nfg = 300"),
+            "`title.fg`: 300 isn't an ANSI index (0-255)"
+        );
+        assert_eq!(
+            error("[title]\nmodifiers = [\"loud\"]"),
+            "`title.modifiers`: unknown modifier `loud`"
+        );
+        assert_eq!(error("title = \"red\""), "`title` should be a table");
+        assert_eq!(
+            error("base = \"solarized\""),
+            "unknown base theme `solarized`"
+        );
+    }
+
+    #[test]
+    fn watcher_picks_up_edits_and_broken_files() {
+        let path = std::env::temp_dir().join(format!("tuwi-theme-{}.toml", process::id()));
+        fs::write(&path, "[title]\nfg = \"red\"").unwrap();
+        let mut watcher = ThemeWatcher::new(&path).interval(Duration::ZERO);
+
+        let theme = watcher.poll().unwrap().unwrap();
+        assert_eq!(theme.title, Style::new().fg(Color::Red));
+        assert!(watcher.poll().is_none());
+
+        // Rewritten straight away, likely within the same timestamp tick.
+        fs::write(&path, "[title]\nfg = \"green\"").unwrap();
+        let theme = watcher.poll().unwrap().unwrap();
+        assert_eq!(theme.title, Style::new().fg(Color::Green));
+
+        fs::write(&path, "[title").unwrap();
+        let e = watcher.poll().unwrap().unwrap_err();
+        assert_eq!(e.path.as_deref(), Some(path.as_path()));
+        assert!(watcher.poll().is_none());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,418 @@
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use super::Theme;
+
+/// A check run against an item's value, attached with [`Form::validator`].
+///
+/// [`Form::validator`]: super::Form::validator
+pub trait Validator: Send + Sync {
+    /// Returns the message to show when `value` is invalid.
+    fn validate(&self, value: &str) -> Result<(), String>;
+}
+
+impl<F> Validator for F
+where
+    F: Fn(&str) -> Result<(), String> + Send + Sync,
+{
+    fn validate(&self, value: &str) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// Fails on empty or whitespace-only values.
+#[derive(Clone)]
+pub struct Required;
+
+impl Validator for Required {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match value.trim().is_empty() {
+            true => Err("Required".to_string()),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Minimum length in characters (grapheme clusters).
+#[derive(Clone)]
+pub struct MinLength(pub usize);
+
+impl Validator for MinLength {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match value.graphemes(true).count() < self.0 {
+            true => Err(format!("Must be at least {} characters", self.0)),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Maximum length in characters (grapheme clusters).
+#[derive(Clone)]
+pub struct MaxLength(pub usize);
+
+impl Validator for MaxLength {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match value.graphemes(true).count() > self.0 {
+            true => Err(format!("Must be at most {} characters", self.0)),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Fails unless the value matches a regular expression.
+#[derive(Clone)]
+pub struct Pattern {
+    pub regex: Regex,
+    pub message: String,
+}
+
+impl Pattern {
+    pub fn new(pattern: &str, message: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            regex: Regex::new(pattern)?,
+            message: message.to_string(),
+        })
+    }
+}
+
+impl Validator for Pattern {
+    fn validate(&self, value: &str) -> Result<(), String> {
+        match self.regex.is_match(value) {
+            true => Ok(()),
+            false => Err(self.message.clone()),
+        }
+    }
+}
+
+/// Set when the value being checked has changed, so a slow
+/// [`AsyncValidator`] can stop early.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A check too slow for the UI thread (e.g. a lookup against a local
+/// service), attached with [`Form::async_validator`].
+///
+/// It runs on a background thread whenever the value changes, and the form
+/// refuses to submit until it has finished.
+///
+/// [`Form::async_validator`]: super::Form::async_validator
+pub trait AsyncValidator: Send + Sync {
+    fn validate(&self, value: &str, cancel: &CancelToken) -> Result<(), String>;
+}
+
+impl<F> AsyncValidator for F
+where
+    F: Fn(&str, &CancelToken) -> Result<(), String> + Send + Sync,
+{
+    fn validate(&self, value: &str, cancel: &CancelToken) -> Result<(), String> {
+        self(value, cancel)
+    }
+}
+
+type CheckResult = Arc<Mutex<Option<Result<(), String>>>>;
+
+/// One item's in-flight or finished background check.
+pub(crate) struct AsyncCheck {
+    pub value: String,
+    result: CheckResult,
+    cancel: CancelToken,
+}
+
+impl AsyncCheck {
+    pub fn spawn(validators: Vec<Arc<dyn AsyncValidator>>, value: &str) -> Self {
+        let check = Self {
+            value: value.to_string(),
+            result: Arc::default(),
+            cancel: CancelToken::default(),
+        };
+
+        let (value, result, cancel) = (
+            check.value.clone(),
+            check.result.clone(),
+            check.cancel.clone(),
+        );
+
+        thread::spawn(move || {
+            // A panicking check fails the value rather than leaving it
+            // pending (and the form unable to submit) forever.
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                validators
+                    .iter()
+                    .try_for_each(|v| v.validate(&value, &cancel))
+            }))
+            .unwrap_or_else(|_| Err("Couldn't be checked".to_string()));
+
+            if !cancel.is_cancelled() {
+                *result.lock().unwrap() = Some(outcome);
+            }
+        });
+
+        check
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.result.lock().unwrap().is_none()
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.result.lock().unwrap().clone()?.err()
+    }
+}
+
+/// A failed [`Rule`], shown on each of the named items.
+#[derive(Clone, Debug)]
+pub struct RuleError {
+    pub names: Vec<String>,
+    pub message: String,
+}
+
+impl RuleError {
+    pub fn new(names: &[&str], message: &str) -> Self {
+        Self {
+            names: names.iter().map(|n| n.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// A check spanning several items, attached with [`Form::rule`].
+///
+/// Rules receive every item's value by name, as from [`Form::slurp`].
+///
+/// [`Form::rule`]: super::Form::rule
+/// [`Form::slurp`]: super::Form::slurp
+pub trait Rule: Send + Sync {
+    fn check(&self, values: &HashMap<String, String>) -> Result<(), RuleError>;
+}
+
+impl<F> Rule for F
+where
+    F: Fn(&HashMap<String, String>) -> Result<(), RuleError> + Send + Sync,
+{
+    fn check(&self, values: &HashMap<String, String>) -> Result<(), RuleError> {
+        self(values)
+    }
+}
+
+/// Fails on `b` unless it has the same value as `a`, e.g. password confirmation.
+pub fn fields_match(a: &str, b: &str, message: &str) -> impl Rule {
+    let (a, b) = (a.to_string(), b.to_string());
+    let error = RuleError::new(&[&b], message);
+    move |values: &HashMap<String, String>| match values.get(&a) == values.get(&b) {
+        true => Ok(()),
+        false => Err(error.clone()),
+    }
+}
+
+/// Fails on all of `names` unless at least one of them is filled in.
+pub fn at_least_one(names: &[&str], message: &str) -> impl Rule {
+    let error = RuleError::new(names, message);
+    move |values: &HashMap<String, String>| match error
+        .names
+        .iter()
+        .any(|n| values.get(n).is_some_and(|v| !v.trim().is_empty()))
+    {
+        true => Ok(()),
+        false => Err(error.clone()),
+    }
+}
+
+/// Marks an item's border as invalid and writes `message` into its bottom edge.
+/// A compact, one-row item gets the message at its end instead.
+pub fn render_error(area: Rect, buf: &mut Buffer, message: &str, style: Style) {
+    if area.height == 1 {
+        let width = (message.width() as u16 + 1).min(area.width / 2);
+        buf.set_stringn(
+            area.right() - width,
+            area.y,
+            format!(" {message}"),
+            width as usize,
+            style,
+        );
+        return;
+    }
+
+    if area.width < 2 || area.height < 2 {
+        return;
+    }
+
+    for edge in [
+        Rect { height: 1, ..area },
+        Rect {
+            y: area.bottom() - 1,
+            height: 1,
+            ..area
+        },
+        Rect { width: 1, ..area },
+        Rect {
+            x: area.right() - 1,
+            width: 1,
+            ..area
+        },
+    ] {
+        buf.set_style(edge, style);
+    }
+
+    buf.set_stringn(
+        area.x + 1,
+        area.bottom() - 1,
+        message,
+        area.width as usize - 2,
+        style,
+    );
+}
+
+/// Draws a spinner in the top-right of an item's border while it's being
+/// checked, in the theme's muted style.
+pub fn render_pending(area: Rect, buf: &mut Buffer, theme: &Theme) {
+    if area.width < 5 {
+        return;
+    }
+
+    let frames = theme.glyphs().spinner;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+
+    buf.set_string(
+        area.right() - 4,
+        area.y,
+        format!(" {} ", frames[(millis / 100) as usize % frames.len()]),
+        theme.muted,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::atomic::AtomicBool, time::Duration};
+
+    use super::*;
+    use crate::widgets::{field_is, Form, Input, Radio, Theme};
+
+    /// Stands in for a lookup against a local service.
+    fn username_free(value: &str, _: &CancelToken) -> Result<(), String> {
+        thread::sleep(Duration::from_millis(20));
+        match value {
+            "taken" => Err("Already taken".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    fn form() -> Form {
+        let mut form = Form::new();
+        form.push(Input::new("user", "User", ""));
+        form
+    }
+
+    #[test]
+    fn waits_for_a_check() {
+        let mut form = form();
+        form.async_validator("user", username_free);
+
+        form.fill([("user", "taken")]);
+        assert!(form.is_pending());
+        assert!(form.wait_for_checks(Duration::from_secs(5)));
+        assert_eq!(
+            form.validate(),
+            vec![("user".to_string(), "Already taken".to_string())]
+        );
+
+        form.fill([("user", "free")]);
+        assert!(form.wait_for_checks(Duration::from_secs(5)));
+        assert!(form.is_valid());
+    }
+
+    #[test]
+    fn the_spinner_follows_the_theme() {
+        let mut form = form();
+        form.set_theme(Theme::monochrome());
+        form.async_validator("user", |_: &str, _: &CancelToken| -> Result<(), String> {
+            thread::sleep(Duration::from_millis(500));
+            Ok(())
+        });
+        form.fill([("user", "someone")]);
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 3));
+        form.ren(buf.area, &mut buf);
+        let spinner = (0..buf.area.width)
+            .map(|x| buf.get(x, 0))
+            .find(|c| Theme::monochrome().glyphs().spinner.contains(&c.symbol()))
+            .expect("no spinner drawn");
+        assert_eq!(spinner.fg, Theme::monochrome().muted.fg.unwrap_or_default());
+    }
+
+    #[test]
+    fn a_panicking_check_fails_instead_of_hanging() {
+        let mut form = form();
+        form.async_validator("user", |_: &str, _: &CancelToken| -> Result<(), String> {
+            panic!("lookup failed")
+        });
+
+        form.fill([("user", "anything")]);
+        assert!(form.wait_for_checks(Duration::from_secs(5)));
+        assert!(!form.is_valid());
+    }
+
+    #[test]
+    fn hidden_and_removed_items_dont_stay_pending() {
+        static RELEASE: AtomicBool = AtomicBool::new(false);
+        let stuck = |_: &str, cancel: &CancelToken| {
+            while !RELEASE.load(Ordering::Relaxed) && !cancel.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+
+            Ok(())
+        };
+
+        let mut form = form();
+        form.push(Radio::new(
+            "show",
+            "Show",
+            vec![("Yes", "y"), ("No", "n")],
+            "y",
+        ));
+        form.visible_when("user", field_is("show", "y"));
+        form.async_validator("user", stuck);
+
+        form.fill([("user", "a")]);
+        assert!(form.is_pending());
+        form.fill([("show", "n")]);
+        assert!(!form.is_pending());
+
+        form.fill([("show", "y"), ("user", "b")]);
+        assert!(form.is_pending());
+        form.remove("user");
+        assert!(!form.is_pending());
+
+        RELEASE.store(true, Ordering::Relaxed);
+    }
+}
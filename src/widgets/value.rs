@@ -0,0 +1,141 @@
+use std::{collections::HashMap, fmt::Display};
+
+/// A typed value read from a [`FormItem`](super::FormItem).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum FormValue {
+    Text(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    List(Vec<String>),
+    /// One map of values per entry of a [`Group`](super::Group).
+    Records(Vec<HashMap<String, FormValue>>),
+    /// The value (not the label) of the selected option.
+    Choice(String),
+    #[default]
+    Null,
+}
+
+impl FormValue {
+    /// The contained string, for `Text` and `Choice`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FormValue::Text(s) | FormValue::Choice(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            FormValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// The contained number, for `Float` and `Int`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FormValue::Float(f) => Some(*f),
+            FormValue::Int(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FormValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            FormValue::List(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    pub fn as_records(&self) -> Option<&[HashMap<String, FormValue>]> {
+        match self {
+            FormValue::Records(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, FormValue::Null)
+    }
+}
+
+/// The string view used by [`Form::slurp`](super::Form::slurp). Lists are
+/// joined with `", "`, records are written `name=value` (sorted by name) and
+/// separated by `"; "`, and `Null` is empty.
+impl Display for FormValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormValue::Text(s) | FormValue::Choice(s) => f.write_str(s),
+            FormValue::Int(i) => write!(f, "{i}"),
+            FormValue::Float(n) => write!(f, "{n}"),
+            FormValue::Bool(b) => write!(f, "{b}"),
+            FormValue::List(l) => f.write_str(&l.join(", ")),
+            FormValue::Records(r) => {
+                let records = r
+                    .iter()
+                    .map(|record| {
+                        let mut fields = record
+                            .iter()
+                            .map(|(k, v)| format!("{k}={v}"))
+                            .collect::<Vec<_>>();
+                        fields.sort();
+                        fields.join(", ")
+                    })
+                    .collect::<Vec<_>>();
+
+                f.write_str(&records.join("; "))
+            }
+            FormValue::Null => Ok(()),
+        }
+    }
+}
+
+impl From<&str> for FormValue {
+    fn from(value: &str) -> Self {
+        FormValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for FormValue {
+    fn from(value: String) -> Self {
+        FormValue::Text(value)
+    }
+}
+
+impl From<i64> for FormValue {
+    fn from(value: i64) -> Self {
+        FormValue::Int(value)
+    }
+}
+
+impl From<f64> for FormValue {
+    fn from(value: f64) -> Self {
+        FormValue::Float(value)
+    }
+}
+
+impl From<bool> for FormValue {
+    fn from(value: bool) -> Self {
+        FormValue::Bool(value)
+    }
+}
+
+impl From<Vec<String>> for FormValue {
+    fn from(value: Vec<String>) -> Self {
+        FormValue::List(value)
+    }
+}
+
+impl From<Vec<HashMap<String, FormValue>>> for FormValue {
+    fn from(value: Vec<HashMap<String, FormValue>>) -> Self {
+        FormValue::Records(value)
+    }
+}
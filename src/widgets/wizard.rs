@@ -0,0 +1,288 @@
+use std::{collections::HashMap, iter, sync::Arc};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{self, Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Widget,
+};
+
+use super::{Form, FormOutcome, FormValue, Theme};
+
+/// Rows the step indicator takes above the page.
+pub(super) const HEADER_HEIGHT: u16 = 2;
+
+/// Picks the page to go to after a [`Wizard`] page from the answers so far,
+/// by name. `None` carries on to the next page in order.
+pub type Branch = Arc<dyn Fn(&HashMap<String, FormValue>) -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct WizardPage {
+    pub name: String,
+    pub title: String,
+    pub form: Form,
+    branch: Option<Branch>,
+}
+
+/// A sequence of [`Form`] pages, answered one at a time.
+///
+/// Submitting a page (its button, or Ctrl+S) or PageDown moves on once the
+/// page is valid, PageUp goes back, and Esc cancels. Submitting the last page
+/// gives every visited page's values together.
+#[derive(Clone, Default)]
+pub struct Wizard {
+    pub title: Option<String>,
+    pub pages: Vec<WizardPage>,
+    pub current: usize,
+    /// Pages visited before the current one, most recent last.
+    history: Vec<usize>,
+    /// Given to pages as they're pushed, once set.
+    theme: Option<Theme>,
+}
+
+impl Wizard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, name: &str, title: &str, mut form: Form) {
+        if let Some(theme) = self.theme {
+            form.set_theme(theme);
+        }
+
+        self.pages.push(WizardPage {
+            name: name.to_string(),
+            title: title.to_string(),
+            form,
+            branch: None,
+        });
+    }
+
+    /// Restyles the wizard and every page, including ones pushed later.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Some(theme);
+        for page in &mut self.pages {
+            page.form.set_theme(theme);
+        }
+    }
+
+    /// Chooses where to go after the page named `name`. See [`Branch`].
+    pub fn branch(
+        &mut self,
+        name: &str,
+        f: impl Fn(&HashMap<String, FormValue>) -> Option<String> + Send + Sync + 'static,
+    ) {
+        if let Some(page) = self.pages.iter_mut().find(|p| p.name == name) {
+            page.branch = Some(Arc::new(f));
+        }
+    }
+
+    /// The page being answered, if there are any.
+    pub fn form(&self) -> Option<&Form> {
+        self.pages.get(self.current).map(|p| &p.form)
+    }
+
+    pub fn form_mut(&mut self) -> Option<&mut Form> {
+        self.pages.get_mut(self.current).map(|p| &mut p.form)
+    }
+
+    /// Every visited page's values, the current one included. Later pages
+    /// win if names are reused.
+    pub fn values(&self) -> HashMap<String, FormValue> {
+        self.history
+            .iter()
+            .chain(iter::once(&self.current))
+            .filter_map(|i| self.pages.get(*i))
+            .flat_map(|p| p.form.values())
+            .collect()
+    }
+
+    /// Moves to the next page, returning `false` if this is the last one.
+    /// Fails, staying put, if the page's [`Branch`] names a page that
+    /// doesn't exist.
+    pub fn next_page(&mut self) -> Result<bool, String> {
+        let Some(page) = self.pages.get(self.current) else {
+            return Ok(false);
+        };
+
+        let next = match page.branch.as_ref().and_then(|b| b(&self.values())) {
+            Some(name) => match self.pages.iter().position(|p| p.name == name) {
+                Some(i) => i,
+                None => return Err(format!("No page named `{name}`")),
+            },
+            None => self.current + 1,
+        };
+
+        if next >= self.pages.len() {
+            return Ok(false);
+        }
+
+        self.history.push(self.current);
+        self.current = next;
+        Ok(true)
+    }
+
+    /// Returns to the previous page, returning `false` on the first one.
+    pub fn previous_page(&mut self) -> bool {
+        match self.history.pop() {
+            Some(i) => {
+                self.current = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Accepts a full [`KeyEvent`], or a bare [`KeyCode`] for older callers.
+    pub fn handle_input(&mut self, k: impl Into<KeyEvent>) -> FormOutcome {
+        let k = k.into();
+        let Some(form) = self.form_mut() else {
+            return FormOutcome::Continue;
+        };
+
+        if k.kind == KeyEventKind::Release {
+            return FormOutcome::Continue;
+        }
+
+        match k.code {
+            KeyCode::PageUp => {
+                self.previous_page();
+                FormOutcome::Continue
+            }
+            KeyCode::PageDown if form.blocked() => FormOutcome::Continue,
+            KeyCode::PageDown => self.advance(),
+            _ => match form.handle_input(k) {
+                FormOutcome::Submitted(_) => self.advance(),
+                outcome => outcome,
+            },
+        }
+    }
+
+    pub fn handle_mouse(&mut self, m: MouseEvent) -> FormOutcome {
+        let Some(form) = self.form_mut() else {
+            return FormOutcome::Continue;
+        };
+
+        match form.handle_mouse(m) {
+            FormOutcome::Submitted(_) => self.advance(),
+            outcome => outcome,
+        }
+    }
+
+    /// Moves on, or gives every page's values after the last one. A broken
+    /// branch is shown on the current page.
+    fn advance(&mut self) -> FormOutcome {
+        match self.next_page() {
+            Ok(true) => FormOutcome::Continue,
+            Ok(false) => FormOutcome::Submitted(self.values()),
+            Err(message) => {
+                if let Some(form) = self.form_mut() {
+                    form.message = Some(message.clone());
+                }
+
+                FormOutcome::Error(message)
+            }
+        }
+    }
+
+    /// "Step n of m" followed by the page titles, with the current one
+    /// highlighted and visited ones left plain.
+    fn header<'a>(&self) -> Line<'a> {
+        let theme = self.theme.unwrap_or_default();
+        let mut spans = vec![Span::raw(format!(
+            "Step {} of {}  ",
+            self.current + 1,
+            self.pages.len()
+        ))];
+
+        for (i, page) in self.pages.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(theme.glyphs().breadcrumb, theme.muted));
+            }
+
+            let style = match i {
+                _ if i == self.current => theme.selection,
+                _ if self.history.contains(&i) => Style::new(),
+                _ => theme.disabled,
+            };
+
+            spans.push(Span::styled(page.title.clone(), style));
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Widget for Wizard {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner = match &self.title {
+            Some(title) => {
+                let block = self.theme.unwrap_or_default().block(title.clone(), false);
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+
+        let Some(form) = self.form() else {
+            return;
+        };
+
+        let layout = Layout::new(
+            layout::Direction::Vertical,
+            vec![Constraint::Length(HEADER_HEIGHT), Constraint::Fill(1)],
+        )
+        .split(inner);
+
+        self.header().render(layout[0], buf);
+        form.ren(layout[1], buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::widgets::Input;
+
+    fn page(name: &str) -> Form {
+        let mut form = Form::new();
+        form.push(Input::new(name, name, ""));
+        form
+    }
+
+    #[test]
+    fn an_empty_wizard_has_no_form() {
+        let mut wizard = Wizard::new();
+        assert!(wizard.form().is_none());
+        assert!(wizard.form_mut().is_none());
+        assert_eq!(wizard.next_page(), Ok(false));
+        assert!(matches!(
+            wizard.handle_input(KeyCode::PageDown),
+            FormOutcome::Continue
+        ));
+        assert!(wizard.values().is_empty());
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 20, 5));
+        wizard.render(buf.area, &mut buf);
+    }
+
+    #[test]
+    fn branching_to_a_missing_page_is_an_error() {
+        let mut wizard = Wizard::new();
+        wizard.push("first", "First", page("a"));
+        wizard.push("second", "Second", page("b"));
+        wizard.branch("first", |_| Some("thrid".to_string()));
+
+        assert_eq!(wizard.next_page(), Err("No page named `thrid`".to_string()));
+        assert_eq!(wizard.current, 0);
+
+        let outcome = wizard.handle_input(KeyCode::PageDown);
+        assert!(matches!(outcome, FormOutcome::Error(m) if m == "No page named `thrid`"));
+        assert_eq!(wizard.current, 0);
+    }
+}
@@ -0,0 +1,83 @@
+use tuwi::{FormValue, TuwiForm};
+
+// Spelled out so it also builds with the `derive` feature's re-export.
+#[derive(tuwi_derive::TuwiForm, Debug, Clone, PartialEq, Default)]
+struct Config {
+    #[tuwi(title = "Server name")]
+    name: String,
+    #[tuwi(secret)]
+    password: String,
+    #[tuwi(choices("tcp", "udp"))]
+    protocol: String,
+    #[tuwi(name = "server.port", range = 1..=65535)]
+    port: u16,
+    #[tuwi(slider, range = 0..=10)]
+    level: i8,
+    max_bytes: u64,
+    ratio: f64,
+    verbose: bool,
+    tags: Vec<String>,
+    #[tuwi(skip)]
+    cache: Option<String>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "tuwi".to_string(),
+        password: "hunter2".to_string(),
+        protocol: "udp".to_string(),
+        port: 8080,
+        level: 3,
+        max_bytes: 1 << 40,
+        ratio: 0.5,
+        verbose: true,
+        tags: vec!["a".to_string(), "b".to_string()],
+        cache: None,
+    }
+}
+
+#[test]
+fn round_trips() {
+    let config = config();
+    let form = config.to_form();
+    assert_eq!(Config::from_form(&form), Ok(config));
+}
+
+#[test]
+fn reads_back_edits() {
+    let mut form = config().to_form();
+    let rejected = form.fill([
+        ("server.port", FormValue::Int(443)),
+        ("protocol", FormValue::from("tcp")),
+        ("verbose", FormValue::from("false")),
+    ]);
+    assert!(rejected.is_empty());
+
+    let config = Config::from_form(&form).unwrap();
+    assert_eq!(config.port, 443);
+    assert_eq!(config.protocol, "tcp");
+    assert!(!config.verbose);
+}
+
+#[test]
+fn rejects_integers_the_widget_cant_hold() {
+    let config = Config {
+        max_bytes: u64::MAX,
+        ..config()
+    };
+
+    let Err(e) = config.try_to_form() else {
+        panic!("u64::MAX fit in a NumInput");
+    };
+
+    assert_eq!(e.field, "max_bytes");
+}
+
+#[test]
+fn conversion_errors_name_the_field() {
+    let mut form = config().to_form();
+    form.fill([("ratio", "half")]);
+
+    let e = Config::from_form(&form).unwrap_err();
+    assert_eq!(e.field, "ratio");
+}